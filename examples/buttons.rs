@@ -5,7 +5,6 @@ extern crate rusttype;
 extern crate xml;
 
 use patchgl::material::components::button::*;
-use patchgl::material;
 use patchgl::flood::*;
 use patchgl::flood::Placement;
 use patchgl::traits::*;
@@ -64,9 +63,7 @@ impl Draw<AppMsg> for AppMdl {
             ],
             click_msg: AppMsg::None,
         });
-        let light_panel = light_plain_flat
-            + Padding::Dual((Length::Full - material::Length::ButtonWidth(button_label.into()).into()) * 0.5,
-                            (Length::Full - material::Length::ButtonHeight.into()) * 0.5)
+        let light_panel = centered(light_plain_flat)
             + Flood::Color(palette.light_background_raised);
 
         let dark_plain_flat = Flood::<AppMsg>::from(Button {
@@ -80,11 +77,14 @@ impl Draw<AppMsg> for AppMdl {
             ],
             click_msg: AppMsg::None,
         });
-        let dark_panel = dark_plain_flat
-            + Padding::Dual((Length::Full - material::Length::ButtonWidth(button_label.into()).into()) * 0.5,
-                            (Length::Full - material::Length::ButtonHeight.into()) * 0.5)
+        let dark_panel = centered(dark_plain_flat)
             + Flood::Color(palette.dark_background_raised);
 
         light_panel + (Position::Right(Length::Full * 0.5), dark_panel)
     }
 }
+
+fn centered(flood: Flood<AppMsg>) -> Flood<AppMsg> {
+    let tracks = vec![Span::Weight(1.0), Span::Intrinsic, Span::Weight(1.0)];
+    Flood::Grid(Grid::Tracks(tracks.clone(), tracks, Length::Zero, Length::Zero), vec![(Cell::at(1, 1), flood)])
+}
@@ -0,0 +1,31 @@
+extern crate arrayvec;
+extern crate cage;
+extern crate patchgl;
+extern crate rusttype;
+extern crate xml;
+
+use patchgl::Color;
+use patchgl::flood::*;
+use patchgl::window;
+use patchgl::window::WindowMsg;
+
+fn main() {
+    window::start(320, 400, |window| {
+        let check = Flood::Canvas(
+            vec![Path::Circle((40.0, 40.0), 36.0), Path::Move(22.0, 40.0), Path::Line(35.0, 53.0), Path::Line(60.0, 28.0)],
+            vec![Ink::Fill(Color::from_hexrgb(0x4c, 0xaf, 0x50)), Ink::Stroke(6.0, Color::white())],
+        );
+        let chart = Flood::Canvas(
+            vec![
+                Path::Move(0.0, 120.0), Path::Line(0.0, 90.0), Path::Line(60.0, 40.0), Path::Line(120.0, 70.0),
+                Path::Line(180.0, 10.0), Path::Line(240.0, 50.0), Path::Line(240.0, 120.0), Path::Close,
+            ],
+            vec![Ink::Fill(Color::from_hexrgb(0xbb, 0xde, 0xfb)), Ink::Stroke(2.0, Color::from_hexrgb(0x19, 0x76, 0xd2))],
+        );
+        let flood = check
+            + (Position::Bottom(Length::Pixels(140.0)), chart)
+            + Padding::Uniform(Length::Spacing * 2.0)
+            + (Stratum::JustBelow, Flood::Color(Color::white()) + Shadow::Clear);
+        window.send(WindowMsg::Flood::<()>(flood)).unwrap();
+    });
+}
@@ -0,0 +1,70 @@
+extern crate arrayvec;
+extern crate cage;
+extern crate patchgl;
+extern crate rusttype;
+extern crate xml;
+
+use patchgl::Color;
+use patchgl::flood::*;
+use patchgl::material::Palette;
+use patchgl::material::components::button::{ButtonEvt, ButtonKind, ButtonMdl, ButtonMsg, FlatButton};
+use patchgl::store::Store;
+use patchgl::traits::*;
+use patchgl::window;
+
+fn main() {
+    window::start(480, 240, |window| {
+        use patchgl::app::App;
+        let app = App::new(AppMdl::update, AppMdl::draw);
+        app.run("Components", AppMdl::default(), window);
+    });
+}
+
+const LABELS: [&str; 3] = ["One", "Two", "Three"];
+
+#[derive(Clone, PartialEq, Debug, Default)]
+struct AppMdl {
+    buttons: Store<ButtonMdl>,
+    clicked: Option<u64>,
+}
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+enum AppMsg {
+    Button(u64, ButtonMsg),
+    Clicked(u64),
+}
+
+impl Update<AppMsg> for AppMdl {
+    fn update(&mut self, msg: AppMsg) {
+        match msg {
+            AppMsg::Button(id, msg) => {
+                self.buttons.update(id, msg);
+            }
+            AppMsg::Clicked(id) => {
+                self.clicked = Some(id);
+            }
+        }
+    }
+}
+
+impl Draw<AppMsg> for AppMdl {
+    fn draw(&self) -> Flood<AppMsg> {
+        let palette = Palette::default();
+        let buttons = LABELS.iter().enumerate()
+            .map(|(i, label)| {
+                let button = FlatButton { kind: ButtonKind::LightColoredFlat(label.to_string()), placement: Placement::Center, palette };
+                (Span::Intrinsic, self.buttons.view(&button, i as u64 + 1, AppMsg::Button, |id, ButtonEvt::Click| AppMsg::Clicked(id)))
+            })
+            .collect::<Vec<_>>();
+        let status = match self.clicked {
+            Some(id) => format!("Clicked {}", LABELS[id as usize - 1]),
+            None => "Click a button".into(),
+        };
+        Flood::Stream(Stack::Column(Length::Spacing, Cross::Place(Placement::Center)), vec![
+            (Span::Intrinsic, Flood::Text(status, Color::black(), Placement::Center)),
+            (Span::Intrinsic, Flood::Stream(Stack::Row(Length::Spacing / 2, Cross::Fill), buttons)),
+        ])
+            + Padding::Uniform(Length::Spacing)
+            + Flood::Color(palette.light_background_raised)
+    }
+}
@@ -0,0 +1,29 @@
+extern crate arrayvec;
+extern crate cage;
+extern crate patchgl;
+extern crate rusttype;
+extern crate xml;
+
+use patchgl::Color;
+use patchgl::flood::*;
+use patchgl::window;
+use patchgl::window::WindowMsg;
+
+fn main() {
+    window::start(640, 400, |window| {
+        let video = Flood::Color(Color::black())
+            + Confine::ratio(16.0 / 9.0).place(Placement::Center, Placement::Start);
+        let avatar = Flood::Color(Color::from_hexrgb(0x90, 0xca, 0xf9))
+            + Corners::Uniform(Length::Half)
+            + Confine::max_size(Length::Pixels(64.0), Length::Pixels(64.0)).with_ratio(1.0).place(Placement::End, Placement::End);
+        let dialog = Flood::Text("Centered".into(), Color::black(), Placement::Center)
+            + Padding::Uniform(Length::Spacing)
+            + (Stratum::JustBelow, Flood::Color(Color::white()) + Corners::Uniform(Length::Pixels(4.0)))
+            + Confine::max_size(Length::Full * 0.5, Length::Pixels(80.0));
+        let flood = dialog
+            + (Stratum::JustBelow, avatar + Padding::Uniform(Length::Spacing))
+            + (Stratum::JustBelow, video)
+            + (Stratum::JustBelow, Flood::Color(Color::grey()));
+        window.send(WindowMsg::Flood::<()>(flood)).unwrap();
+    });
+}
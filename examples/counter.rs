@@ -59,7 +59,7 @@ enum AppMsg {
 fn draw(model: &Model) -> Flood<AppMsg> {
     let edge_padding = Padding::Uniform(Length::Spacing);
     let palette = &model.material.palette;
-    let background = Flood::Color(palette.light_background);
+    let background = Flood::Color(palette.light_background) + Shadow::Clear;
 
     let text = format!("{:+}", model.count);
     let body = Flood::Text(text, palette.primary, Placement::Center);
@@ -92,12 +92,8 @@ fn draw(model: &Model) -> Flood<AppMsg> {
 }
 
 fn draw_bar(contents: Vec<Flood<AppMsg>>) -> Flood<AppMsg> {
-    use ::patchgl::color::argb::TRANSPARENT;
-    let enumeration = contents.into_iter().enumerate().collect::<Vec<_>>();
-    enumeration.into_iter().fold(
-        Flood::Color(TRANSPARENT),
-        |bar, (i, button)| {
-            let segment = button + Padding::Horizontal(Length::Spacing / 4);
-            bar + (Position::Right(Length::Full / (i as u32 + 1)), segment)
-        })
+    let children = contents.into_iter()
+        .map(|button| (Span::Weight(1.0), button + Padding::Horizontal(Length::Spacing / 4)))
+        .collect();
+    Flood::Stream(Stack::Row(Length::Zero, Cross::Fill), children)
 }
@@ -0,0 +1,89 @@
+extern crate arrayvec;
+extern crate cage;
+extern crate patchgl;
+extern crate rusttype;
+extern crate xml;
+
+use patchgl::{Color, TouchMsg};
+use patchgl::flood::*;
+use patchgl::traits::*;
+use patchgl::window;
+use std::sync::Arc;
+
+fn main() {
+    window::start(480, 240, |window| {
+        use patchgl::app::App;
+        let app = App::new(AppMdl::update, AppMdl::draw);
+        app.run("Embed", AppMdl::default(), window);
+    });
+}
+
+// A self-contained counter that knows nothing about the app embedding it.
+#[derive(Clone, PartialEq, Debug)]
+struct Counter {
+    id: u64,
+    count: u32,
+}
+
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+enum CounterMsg {
+    Increment,
+    None,
+}
+
+impl Update<CounterMsg> for Counter {
+    fn update(&mut self, msg: CounterMsg) {
+        if msg == CounterMsg::Increment {
+            self.count += 1;
+        }
+    }
+}
+
+impl Draw<CounterMsg> for Counter {
+    fn draw(&self) -> Flood<CounterMsg> {
+        let sensor = Sensor::Touch(self.id, Arc::new(|touch_msg: TouchMsg| match touch_msg {
+            TouchMsg::End(_, _, _) => CounterMsg::Increment,
+            _ => CounterMsg::None,
+        }));
+        Flood::Text(format!("Tapped {}", self.count), Color::black(), Placement::Center)
+            + Padding::Uniform(Length::Spacing)
+            + (Stratum::JustBelow, Flood::Color(Color::custom_white(0.9)) + Corners::Uniform(Length::Pixels(4.0)))
+            + sensor
+    }
+}
+
+#[derive(Clone, PartialEq, Debug)]
+struct AppMdl {
+    left: Counter,
+    right: Counter,
+}
+
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+enum AppMsg {
+    Left(CounterMsg),
+    Right(CounterMsg),
+}
+
+impl Default for AppMdl {
+    fn default() -> Self {
+        AppMdl { left: Counter { id: 1, count: 0 }, right: Counter { id: 2, count: 0 } }
+    }
+}
+
+impl Update<AppMsg> for AppMdl {
+    fn update(&mut self, msg: AppMsg) {
+        match msg {
+            AppMsg::Left(msg) => self.left.update(msg),
+            AppMsg::Right(msg) => self.right.update(msg),
+        }
+    }
+}
+
+impl Draw<AppMsg> for AppMdl {
+    fn draw(&self) -> Flood<AppMsg> {
+        Flood::Stream(Stack::Row(Length::Spacing, Cross::Fill), vec![
+            (Span::Weight(1.0), self.left.draw().map(AppMsg::Left)),
+            (Span::Weight(1.0), self.right.draw().map(AppMsg::Right)),
+        ]) + Padding::Uniform(Length::Spacing)
+    }
+}
@@ -8,6 +8,7 @@ use patchgl::material::entry::Entry;
 use patchgl::material::entry;
 use patchgl::material;
 use patchgl::flood::*;
+use patchgl::traits::*;
 
 fn main() {
     use patchgl::window;
@@ -38,13 +39,10 @@ impl Default for Mdl {
 
 fn draw(mdl: &Mdl) -> Flood<Msg> {
     let entry = Entry {
-        msg_wrap: Msg::EntryMsg,
-        id: 26,
-        mdl: mdl.entry_mdl.clone(),
         label: "Label".into(),
         placeholder: Some("Placeholder".into()),
     };
-    let entry_flood = entry::flood(entry);
+    let entry_flood = entry.view(26, &mdl.entry_mdl, Msg::EntryMsg, |evt| match evt {});
     entry_flood
         + Padding::Dual(Length::Spacing, Length::Full * 0.35)
         + (Stratum::JustBelow, Flood::Color(material::Color::LightBackground.into()))
@@ -52,5 +50,5 @@ fn draw(mdl: &Mdl) -> Flood<Msg> {
 
 fn update(mdl: &mut Mdl, msg: Msg) {
     let Msg::EntryMsg(entry_msg) = msg;
-    entry::update(&mut mdl.entry_mdl, entry_msg);
+    mdl.entry_mdl.update(entry_msg);
 }
@@ -0,0 +1,31 @@
+extern crate arrayvec;
+extern crate cage;
+extern crate patchgl;
+extern crate rusttype;
+extern crate xml;
+
+use patchgl::Color;
+use patchgl::flood::*;
+use patchgl::window;
+use patchgl::window::WindowMsg;
+
+fn main() {
+    window::start(320, 400, |window| {
+        let header = Flood::Gradient(Gradient::Linear(135.0, vec![
+            (0.0, Color::from_hexrgb(0x3f, 0x51, 0xb5)),
+            (1.0, Color::from_hexrgb(0x00, 0xbc, 0xd4)),
+        ]));
+        let spotlight = Flood::Gradient(Gradient::Radial((0.5, 0.4), 0.75, vec![
+            (0.0, Color::white()),
+            (0.6, Color::custom_white(0.8)),
+            (1.0, Color::custom_white(0.5)),
+        ]));
+        let scrim = Flood::Gradient(Gradient::Linear(180.0, vec![
+            (0.5, Color::new(0.0, 0.0, 0.0, 0.0)),
+            (1.0, Color::new(0.6, 0.0, 0.0, 0.0)),
+        ]));
+        let flood = scrim
+            + (Stratum::JustBelow, spotlight + (Position::Top(Length::Full / 4), header));
+        window.send(WindowMsg::Flood::<()>(flood)).unwrap();
+    });
+}
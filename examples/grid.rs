@@ -0,0 +1,34 @@
+extern crate arrayvec;
+extern crate cage;
+extern crate patchgl;
+extern crate rusttype;
+extern crate xml;
+
+use patchgl::Color;
+use patchgl::flood::*;
+use patchgl::window;
+use patchgl::window::WindowMsg;
+
+fn main() {
+    window::start(480, 360, |window| {
+        let tile = |red: u8, green: u8, blue: u8| Flood::Color(Color::from_hexrgb(red, green, blue));
+        let label = |text: &str| Flood::Text(text.into(), Color::black(), Placement::Start);
+        let grid = Grid::Tracks(
+            vec![Span::Intrinsic, Span::Weight(1.0), Span::Weight(1.0)],
+            vec![Span::Fixed(Length::Pixels(32.0)), Span::Weight(1.0), Span::Fraction(0.25)],
+            Length::Spacing / 2,
+            Length::Spacing / 2,
+        );
+        let flood = Flood::Grid(grid, vec![
+            (Cell::at(0, 0), label("Header")),
+            (Cell::at(1, 0).span(2, 1), tile(0x90, 0xca, 0xf9)),
+            (Cell::at(0, 1).span(1, 2), tile(0xa5, 0xd6, 0xa7)),
+            (Cell::at(1, 1), tile(0xff, 0xcc, 0x80)),
+            (Cell::at(2, 1).align(Cross::Place(Placement::End), Cross::Fill), label("Aligned")),
+            (Cell::at(1, 2).span(2, 1), tile(0xce, 0x93, 0xd8)),
+        ])
+            + Padding::Uniform(Length::Spacing)
+            + (Stratum::JustBelow, Flood::Color(Color::white()));
+        window.send(WindowMsg::Flood::<()>(flood)).unwrap();
+    });
+}
@@ -0,0 +1,29 @@
+extern crate arrayvec;
+extern crate cage;
+extern crate patchgl;
+extern crate rusttype;
+extern crate xml;
+
+use patchgl::{Bitmap, Color, ImageSource};
+use patchgl::flood::*;
+use patchgl::window;
+use patchgl::window::WindowMsg;
+use std::sync::Arc;
+
+fn main() {
+    window::start(320, 400, |window| {
+        let checkers = (0..16 * 16)
+            .flat_map(|i| if (i % 16 + i / 16) % 2 == 0 { vec![0x30, 0x30, 0x30, 0xff] } else { vec![0xe0, 0xe0, 0xe0, 0xff] })
+            .collect::<Vec<u8>>();
+        let source = ImageSource::Rgba(Arc::new(Bitmap::new(16, 16, checkers)));
+        let images = vec![
+            Flood::Image(source.clone(), Fit::Contain, Color::white()) + Padding::Uniform(Length::Spacing / 2),
+            Flood::Image(source.clone(), Fit::Center, Color::white()) + Padding::Uniform(Length::Spacing / 2),
+            Flood::Image(source, Fit::Fill, Color::from_hexrgb(0x80, 0xc0, 0xff)) + Padding::Uniform(Length::Spacing / 2),
+        ];
+        let flood = patchgl::flood::bar(images)
+            + Padding::Uniform(Length::Spacing)
+            + (Stratum::JustBelow, Flood::Color(Color::white()) + Shadow::Clear);
+        window.send(WindowMsg::Flood::<()>(flood)).unwrap();
+    });
+}
@@ -62,18 +62,9 @@ impl Draw<AppMsg> for AppMdl {
                 + Flood::Color(palette.light_background)
         };
 
-        let nav_track: Flood<AppMsg> = NavTrack {
-            nav_track_msg_wrap: AppMsg::NavTrackMsg,
-            id: 11,
-            palette,
-            mdl: &self.nav_track_mdl,
-            nav_track_evt_wrap: |nav_track_evt| {
-                match nav_track_evt {
-                    NavTrackEvt::ItemSelected(selection) => AppMsg::ItemSelected(selection),
-                }
-            },
-            items,
-        }.into();
+        let nav_track = NavTrack { palette: palette.clone(), items }.view(11, &self.nav_track_mdl, AppMsg::NavTrackMsg, |evt| match evt {
+            NavTrackEvt::ItemSelected(selection) => AppMsg::ItemSelected(selection),
+        });
 
         detail + (Position::Left(Length::Full * 0.2), nav_track)
     }
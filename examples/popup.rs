@@ -0,0 +1,26 @@
+extern crate arrayvec;
+extern crate cage;
+extern crate patchgl;
+extern crate rusttype;
+extern crate xml;
+
+use patchgl::Color;
+use patchgl::flood::*;
+use patchgl::window;
+use patchgl::window::WindowMsg;
+
+fn main() {
+    window::start(320, 400, |window| {
+        let menu = Flood::Text("Menu".into(), Color::black(), Placement::Start)
+            + Padding::Uniform(Length::Spacing / 2)
+            + (Stratum::JustBelow, Flood::Color(Color::white()) + Corners::Uniform(Length::Pixels(4.0)));
+        let button = Flood::Text("Open".into(), Color::white(), Placement::Center)
+            + Padding::Uniform(Length::Spacing / 2)
+            + (Stratum::JustBelow, Flood::Color(Color::from_hexrgb(0x3f, 0x51, 0xb5)))
+            + (Popup::Below(Length::Full * 2.0, Length::Pixels(120.0)), menu);
+        let flood = Flood::Color(Color::custom_white(0.96))
+            + (Position::Bottom(Length::Pixels(40.0)), button + Padding::Horizontal(Length::Spacing))
+            + Padding::Uniform(Length::Spacing);
+        window.send(WindowMsg::Flood::<()>(flood)).unwrap();
+    });
+}
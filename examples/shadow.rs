@@ -4,7 +4,7 @@ extern crate patchgl;
 extern crate rusttype;
 extern crate xml;
 
-use patchgl::Color;
+use patchgl::{Color, Lighting};
 use patchgl::flood::*;
 use patchgl::window;
 use patchgl::window::WindowMsg;
@@ -29,7 +29,8 @@ fn main() {
         let flood = bar1
             + (Position::Bottom(Length::Half), bar2)
             + Padding::Dual(Length::Spacing, Length::Full / 4)
-            + (Stratum::JustBelow, Flood::Color(Color::white()));
+            + (Stratum::JustBelow, Flood::Color(Color::white()) + Shadow::Clear);
+        window.send(WindowMsg::Lighting(Lighting { softness: 0.75, ..Default::default() })).unwrap();
         window.send(WindowMsg::Flood::<()>(flood)).unwrap();
     });
 }
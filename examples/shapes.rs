@@ -0,0 +1,29 @@
+extern crate arrayvec;
+extern crate cage;
+extern crate patchgl;
+extern crate rusttype;
+extern crate xml;
+
+use patchgl::Color;
+use patchgl::flood::*;
+use patchgl::window;
+use patchgl::window::WindowMsg;
+
+fn main() {
+    window::start(320, 400, |window| {
+        let card = Flood::Color(Color::white())
+            + Corners::Uniform(Length::Pixels(4.0))
+            + Padding::Behind(Length::CardApproach);
+        let chip = Flood::Color(Color::custom_white(0.88))
+            + Corners::Uniform(Length::Half);
+        let outline = Flood::Color(Color { a: 0.0, ..Color::white() })
+            + Border::Solid(Length::Pixels(1.0), Color::custom_black(0.38))
+            + Corners::Each(Length::Pixels(4.0), Length::Pixels(4.0), Length::Zero, Length::Zero);
+        let flood = card
+            + (Position::Bottom(Length::Third), chip + Padding::Uniform(Length::Spacing))
+            + (Position::Bottom(Length::Third), outline + Padding::Uniform(Length::Spacing))
+            + Padding::Uniform(Length::Spacing)
+            + (Stratum::JustBelow, Flood::Color(Color::custom_white(0.96)) + Shadow::Clear);
+        window.send(WindowMsg::Flood::<()>(flood)).unwrap();
+    });
+}
@@ -0,0 +1,35 @@
+extern crate arrayvec;
+extern crate cage;
+extern crate patchgl;
+extern crate rusttype;
+extern crate xml;
+
+use patchgl::Color;
+use patchgl::flood::*;
+use patchgl::window;
+use patchgl::window::WindowMsg;
+
+fn main() {
+    window::start(320, 400, |window| {
+        let chip = |text: &str| {
+            Flood::Text(text.into(), Color::black(), Placement::Center)
+                + Padding::Horizontal(Length::Spacing / 2)
+                + (Stratum::JustBelow, Flood::Color(Color::custom_white(0.88)) + Corners::Uniform(Length::Half))
+        };
+        let chips = Flood::Stream(Stack::Row(Length::Spacing / 2, Cross::Fill), vec![
+            (Span::Intrinsic, chip("Rust")),
+            (Span::Intrinsic, chip("OpenGL")),
+            (Span::Intrinsic, chip("Layout")),
+        ]);
+        let rows = Flood::Stream(Stack::Column(Length::Spacing, Cross::Fill), vec![
+            (Span::Fixed(Length::Pixels(32.0)), chips),
+            (Span::Weight(2.0), Flood::Color(Color::from_hexrgb(0x90, 0xca, 0xf9))),
+            (Span::Weight(1.0), Flood::Color(Color::from_hexrgb(0xa5, 0xd6, 0xa7))),
+            (Span::Fraction(0.25), Flood::Color(Color::from_hexrgb(0xff, 0xcc, 0x80))),
+        ]);
+        let flood = rows
+            + Padding::Uniform(Length::Spacing)
+            + (Stratum::JustBelow, Flood::Color(Color::white()) + Shadow::Clear);
+        window.send(WindowMsg::Flood::<()>(flood)).unwrap();
+    });
+}
@@ -22,11 +22,12 @@ struct Mdl {
 }
 
 use patchgl::material::components::button_bar::*;
+use patchgl::traits::*;
 
 fn update(mdl: &mut Mdl, msg: Msg) {
     match msg {
         Msg::ButtonBarMsg(button_bar_msg) => {
-            update_button_bar(&mut mdl.button_bar_mdl, button_bar_msg);
+            mdl.button_bar_mdl.update(button_bar_msg);
         }
         Msg::Continue => {
             mdl.active_index = (mdl.active_index + 1).min(3);
@@ -54,64 +55,35 @@ fn draw(mdl: &Mdl) -> Flood<Msg> {
     let palette = Palette::default();
     let buttons = if mdl.active_index == 0 {
         vec![
-            Button {
-                id: 99,
-                label: "Start".into(),
-                intent: ButtonIntent::Call,
-                click_msg: Msg::Continue,
-            },
+            Button { id: 99, label: "Start".into(), intent: ButtonIntent::Call },
         ]
     } else if mdl.active_index == 3 {
         vec![
-            Button {
-                id: 99,
-                label: "Done".into(),
-                intent: ButtonIntent::Call,
-                click_msg: Msg::Done,
-            },
-            Button {
-                id: 100,
-                label: "Back".into(),
-                intent: ButtonIntent::Provide,
-                click_msg: Msg::Back,
-            },
+            Button { id: 99, label: "Done".into(), intent: ButtonIntent::Call },
+            Button { id: 100, label: "Back".into(), intent: ButtonIntent::Provide },
         ]
     } else {
         vec![
-            Button {
-                id: 99,
-                label: "Continue".into(),
-                intent: ButtonIntent::Call,
-                click_msg: Msg::Continue,
-            },
-            Button {
-                id: 100,
-                label: "Back".into(),
-                intent: ButtonIntent::Provide,
-                click_msg: Msg::Back,
-            },
+            Button { id: 99, label: "Continue".into(), intent: ButtonIntent::Call },
+            Button { id: 100, label: "Back".into(), intent: ButtonIntent::Provide },
         ]
     };
-    let button_bar: Flood<Msg> = ButtonBar {
-        msg_wrap: Msg::ButtonBarMsg,
-        palette: &palette,
-        button_bar_mdl: &mdl.button_bar_mdl,
-        buttons,
-    }.into();
+    let last = mdl.active_index == 3;
+    let button_bar = ButtonBar { palette, buttons }.view(98, &mdl.button_bar_mdl, Msg::ButtonBarMsg, move |evt| match evt {
+        ButtonBarEvt::Click(99) if last => Msg::Done,
+        ButtonBarEvt::Click(99) => Msg::Continue,
+        ButtonBarEvt::Click(_) => Msg::Back,
+    });
     let content = Flood::Color(palette.primary) + (Position::Bottom(Length::Spacing * 3), button_bar);
-    let stepper: Flood<Msg> = Stepper {
-        palette: &palette,
-        id: vec![1],
-        active_index: mdl.active_index as usize,
-        active_content: content,
-        steps: vec![
-            Step { label: "Fee" },
-            Step { label: "Fi" },
-            Step { label: "Fo" },
-            Step { label: "Fum" },
-        ],
-    }.into();
-    stepper
+    let details = content
+        + (Stratum::JustBelow, Flood::Color(palette.light_background_raised))
+        + Padding::Behind(Length::CardApproach);
+    let steps = ["Fee", "Fi", "Fo", "Fum"].iter().map(|label| Step { label: label.to_string() }).collect();
+    let stepper: Flood<Msg> = Stepper { palette, active_index: mdl.active_index as usize, steps }
+        .view(1, &StepperMdl::default(), |msg| match msg {}, |evt| match evt {});
+    details
+        + (Position::Top(Length::Spacing / 2), Flood::Color(palette.transparent))
+        + (Position::Top(Length::Full * 0.10), stepper)
         + Padding::Uniform(Length::Spacing * 1.5)
         + (Stratum::JustBelow, Flood::Color(palette.light_background))
 }
@@ -1,6 +1,6 @@
 use std::convert::Into;
 
-#[derive(Copy, Clone, Debug)]
+#[derive(Copy, Clone, PartialEq, Debug)]
 pub struct Anchor {
     pub x: f32,
     pub y: f32,
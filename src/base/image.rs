@@ -0,0 +1,102 @@
+use png;
+use std::fmt;
+use std::fs::File;
+use std::hash::{Hash, Hasher};
+use std::io;
+use std::io::Read;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct Bitmap {
+    pub width: u32,
+    pub height: u32,
+    pub rgba: Vec<u8>,
+}
+
+impl Bitmap {
+    pub fn new(width: u32, height: u32, rgba: Vec<u8>) -> Self {
+        assert_eq!(rgba.len(), width as usize * height as usize * 4, "rgba length");
+        Bitmap { width, height, rgba }
+    }
+
+    pub fn from_png<R: Read>(reader: R) -> io::Result<Self> {
+        let mut decoder = png::Decoder::new(reader);
+        decoder.set(png::Transformations::EXPAND | png::Transformations::STRIP_16);
+        let (info, mut png_reader) = decoder.read_info().map_err(invalid_data)?;
+        let mut pixels = vec![0u8; info.buffer_size()];
+        png_reader.next_frame(&mut pixels).map_err(invalid_data)?;
+        let rgba = match info.color_type {
+            png::ColorType::RGBA => pixels,
+            png::ColorType::RGB => pixels.chunks(3).flat_map(|p| vec![p[0], p[1], p[2], 255]).collect(),
+            png::ColorType::GrayscaleAlpha => pixels.chunks(2).flat_map(|p| vec![p[0], p[0], p[0], p[1]]).collect(),
+            png::ColorType::Grayscale => pixels.iter().flat_map(|&p| vec![p, p, p, 255]).collect(),
+            png::ColorType::Indexed => return Err(invalid_data("unexpanded indexed png")),
+        };
+        Ok(Bitmap::new(info.width, info.height, rgba))
+    }
+}
+
+fn invalid_data<E: fmt::Display>(error: E) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, error.to_string())
+}
+
+// Sources double as image cache keys. In-memory bitmaps are the same source only
+// when they share the same allocation.
+#[derive(Clone)]
+pub enum ImageSource {
+    Rgba(Arc<Bitmap>),
+    Png(PathBuf),
+}
+
+impl ImageSource {
+    pub fn load(&self) -> io::Result<Arc<Bitmap>> {
+        match self {
+            &ImageSource::Rgba(ref bitmap) => Ok(bitmap.clone()),
+            &ImageSource::Png(ref path) => Bitmap::from_png(File::open(path)?).map(Arc::new),
+        }
+    }
+}
+
+impl PartialEq for ImageSource {
+    fn eq(&self, other: &ImageSource) -> bool {
+        match (self, other) {
+            (&ImageSource::Rgba(ref a), &ImageSource::Rgba(ref b)) => Arc::ptr_eq(a, b),
+            (&ImageSource::Png(ref a), &ImageSource::Png(ref b)) => a == b,
+            _ => false,
+        }
+    }
+}
+
+impl Eq for ImageSource {}
+
+impl Hash for ImageSource {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        match self {
+            &ImageSource::Rgba(ref bitmap) => (&**bitmap as *const Bitmap as usize).hash(state),
+            &ImageSource::Png(ref path) => path.hash(state),
+        }
+    }
+}
+
+impl fmt::Debug for ImageSource {
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        match self {
+            &ImageSource::Rgba(ref bitmap) => write!(f, "ImageSource::Rgba({}x{})", bitmap.width, bitmap.height),
+            &ImageSource::Png(ref path) => write!(f, "ImageSource::Png({:?})", path),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rgba_sources_match_by_allocation() {
+        let bitmap = Arc::new(Bitmap::new(1, 1, vec![0, 0, 0, 255]));
+        let twin = Arc::new(Bitmap::new(1, 1, vec![0, 0, 0, 255]));
+        assert_eq!(ImageSource::Rgba(bitmap.clone()), ImageSource::Rgba(bitmap.clone()));
+        assert_ne!(ImageSource::Rgba(bitmap), ImageSource::Rgba(twin));
+    }
+}
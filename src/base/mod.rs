@@ -1,5 +1,6 @@
 pub use ::color::{Color, WebColor, X11Color};
 pub use self::alignment::Alignment;
+pub use self::image::{Bitmap, ImageSource};
 pub use self::rectangle::*;
 pub use self::sdf::*;
 pub use self::typeface::Typeface;
@@ -8,6 +9,7 @@ mod sdf;
 mod rectangle;
 mod typeface;
 mod alignment;
+mod image;
 mod channel_adapter;
 
 #[cfg(test)]
@@ -1,4 +1,4 @@
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, PartialEq)]
 pub struct Rectangle {
     pub left: f32,
     pub right: f32,
@@ -10,4 +10,21 @@ impl Rectangle {
     pub fn new_unit() -> Self {
         Rectangle { left: 0.0, right: 1.0, top: 0.0, bottom: 1.0 }
     }
+
+    pub fn intersect(&self, other: &Rectangle) -> Self {
+        Rectangle {
+            left: self.left.max(other.left),
+            right: self.right.min(other.right),
+            top: self.top.max(other.top),
+            bottom: self.bottom.min(other.bottom),
+        }
+    }
+
+    pub fn contains(&self, x: f32, y: f32) -> bool {
+        self.left <= x && x < self.right && self.top <= y && y < self.bottom
+    }
+
+    pub fn to_gl(&self) -> [f32; 4] {
+        [self.left, self.top, self.right, self.bottom]
+    }
 }
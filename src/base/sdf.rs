@@ -1,7 +1,11 @@
 use std::fmt;
 
 pub const SDF_SIZE: usize = 64;
+pub const SDF_SPREAD: usize = 6;
 
+// Fields come in two encodings. Fields filled by new_far and new_near hold plain distances,
+// where 255 is far away and 0 is on the shape. Fields built by from_mask are signed around
+// an outline at 128, where 255 is deep inside the shape and 0 is far outside it.
 #[derive(Copy)]
 pub struct SignedDistanceField {
     distances: [u8; SDF_SIZE * SDF_SIZE]
@@ -40,6 +44,44 @@ impl SignedDistanceField {
         let index = Self::get_index(column, row);
         self.distances[index] = distance
     }
+    pub fn distances(&self) -> &[u8] {
+        &self.distances
+    }
+
+    // Measures each cell's distance to the mask outline, up to SDF_SPREAD cells away. The
+    // outline sits at 128 with larger values inside the mask, so 255 means deep inside here
+    // rather than far away as in new_far. Cells off the grid count as outside.
+    pub fn from_mask<F>(is_inside: F) -> Self where
+        F: Fn(usize, usize) -> bool
+    {
+        let spread = SDF_SPREAD as isize;
+        let size = SDF_SIZE as isize;
+        let mut sdf = Self::new(128u8);
+        for row in 0..SDF_SIZE {
+            for column in 0..SDF_SIZE {
+                let inside = is_inside(column, row);
+                let mut nearest = spread as f32 + 0.5;
+                for dy in -spread..spread + 1 {
+                    for dx in -spread..spread + 1 {
+                        let (other_column, other_row) = (column as isize + dx, row as isize + dy);
+                        let other_inside = if other_column < 0 || other_row < 0 || other_column >= size || other_row >= size {
+                            false
+                        } else {
+                            is_inside(other_column as usize, other_row as usize)
+                        };
+                        if other_inside != inside {
+                            nearest = nearest.min(((dx * dx + dy * dy) as f32).sqrt());
+                        }
+                    }
+                }
+                let outline_distance = (nearest - 0.5).min(spread as f32);
+                let signed_distance = if inside { outline_distance } else { -outline_distance };
+                let encoded = 128.0 + signed_distance * 127.0 / spread as f32;
+                sdf.put_distance(column, row, encoded.round().max(0.0).min(255.0) as u8);
+            }
+        }
+        sdf
+    }
 }
 
 #[cfg(test)]
@@ -63,4 +105,18 @@ mod tests {
         let index = SignedDistanceField::get_index(0, 1);
         assert_eq!(SDF_SIZE, index)
     }
+
+    #[test]
+    fn sdf_from_empty_mask_is_far_outside() {
+        let sdf = SignedDistanceField::from_mask(|_, _| false);
+        assert!(sdf.distances().iter().all(|&distance| distance == 1));
+    }
+
+    #[test]
+    fn sdf_from_mask_crosses_outline_at_mask_edge() {
+        let sdf = SignedDistanceField::from_mask(|column, _| column < SDF_SIZE / 2);
+        assert!(sdf.get_distance(SDF_SIZE / 2 - 1, 0) > 128);
+        assert!(sdf.get_distance(SDF_SIZE / 2, 0) < 128);
+        assert_eq!(255, sdf.get_distance(10, 10));
+    }
 }
@@ -1,12 +1,15 @@
 use super::{Anchor, Color, Sigil, WebColor};
+use base::Rectangle;
 
-#[derive(Debug)]
+#[derive(PartialEq, Debug)]
 pub struct Block {
     pub sigil: Sigil,
     pub width: f32,
     pub height: f32,
     pub approach: f32,
     pub anchor: Anchor,
+    pub casts_shadow: bool,
+    pub clip: Option<Rectangle>,
 }
 
 impl Default for Block {
@@ -17,6 +20,8 @@ impl Default for Block {
             height: 0.0,
             approach: 0.0,
             anchor: Default::default(),
+            casts_shadow: true,
+            clip: None,
         };
         block
     }
@@ -27,6 +32,6 @@ impl Block {
         let (left, top) = self.anchor.into();
         let (right, bottom) = (left + self.width, top + self.height);
         let is_hit = left <= x && x < right && top <= y && y < bottom;
-        is_hit
+        is_hit && self.clip.map_or(true, |clip| clip.contains(x, y))
     }
 }
@@ -1,8 +1,11 @@
 use ::TouchMsg;
+use ::screen::MAX_APPROACH;
 use ::window::BlockRange;
 use std::fmt;
 use std::sync::Arc;
 use super::Length;
+use ::Color;
+use ::scribe::Scribe;
 use super::Signal;
 pub use self::timeout::*;
 use ::flood::Version;
@@ -90,11 +93,41 @@ impl<MsgT> fmt::Debug for Sensor<MsgT> where MsgT: Clone + fmt::Debug
     }
 }
 
+impl<MsgT> Sensor<MsgT> where
+    MsgT: Clone + 'static
+{
+    pub fn map<NewMsgT>(self, f: Arc<Fn(MsgT) -> NewMsgT + Send + Sync>) -> Sensor<NewMsgT> where
+        NewMsgT: Clone + 'static
+    {
+        match self {
+            Sensor::Touch(tag, adapter) => Sensor::Touch(tag, Arc::new(move |touch_msg: TouchMsg| f(adapter(touch_msg)))),
+            Sensor::Input(adapter) => Sensor::Input(Arc::new(move |input: Input| f(adapter(input)))),
+            Sensor::Signal(Signal { id, version: Version { value, counter } }) => {
+                Sensor::Signal(Signal { id, version: Version { value: f(value), counter } })
+            }
+            Sensor::Timeout(Version { value: Timeout { id, msg, duration }, counter }) => {
+                Sensor::Timeout(Version { value: Timeout { id, msg: f(msg), duration }, counter })
+            }
+        }
+    }
+}
+
 #[derive(Clone)]
 pub enum Raft<MsgT> {
     RangeAdapter(u64, Arc<Fn(u64, &BlockRange) -> MsgT + Send + Sync>)
 }
 
+impl<MsgT> Raft<MsgT> where
+    MsgT: 'static
+{
+    pub fn map<NewMsgT>(self, f: Arc<Fn(MsgT) -> NewMsgT + Send + Sync>) -> Raft<NewMsgT> where
+        NewMsgT: 'static
+    {
+        let Raft::RangeAdapter(tag, adapter) = self;
+        Raft::RangeAdapter(tag, Arc::new(move |tag: u64, range: &BlockRange| f(adapter(tag, range))))
+    }
+}
+
 impl<MsgT> fmt::Debug for Raft<MsgT> {
     fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
         let &Raft::RangeAdapter(tag, _) = self;
@@ -105,16 +138,272 @@ impl<MsgT> fmt::Debug for Raft<MsgT> {
 #[derive(Clone, PartialEq, Debug)]
 pub enum Stratum {
     JustBelow,
+    SamePlane,
+    Elevation(f32),
+    Overlay,
 }
 
+// Regular content stays below the overlay plane so overlays cover all of it.
+pub const OVERLAY_APPROACH: f32 = MAX_APPROACH * 0.75;
+
 impl Stratum {
     pub fn add_to(&self, rear_approach: f32) -> f32 {
-        match self {
+        self.add_to_range(rear_approach, rear_approach)
+    }
+
+    // Places the near layer given the approach of the range and of the far layer's
+    // nearest block. Blocks on the same plane paint and hit-test in flood order. An
+    // elevation never sinks behind the far layer, and layers below the overlay plane
+    // stay below it however deep they stack: steps past half the gap to the overlay
+    // plane are squeezed into the next quarter, so nested layers still separate.
+    pub fn add_to_range(&self, base_approach: f32, rear_approach: f32) -> f32 {
+        let approach = match self {
             &Stratum::JustBelow => rear_approach + 0.001,
+            &Stratum::SamePlane => rear_approach,
+            &Stratum::Elevation(elevation) => (base_approach + elevation).max(rear_approach),
+            &Stratum::Overlay => return (rear_approach + 0.001).max(OVERLAY_APPROACH),
+        };
+        let half_gap = (OVERLAY_APPROACH - rear_approach) / 2.0;
+        let excess = approach - rear_approach - half_gap;
+        if half_gap > 0.0 && excess > 0.0 {
+            rear_approach + half_gap + half_gap / 2.0 * excess / (excess + half_gap)
+        } else {
+            approach
+        }
+    }
+}
+
+// Canvas paths use coordinates local to the canvas block. Angles are in degrees, clockwise
+// from pointing right. An arc continues the current subpath from its last point while a
+// circle is a closed subpath of its own.
+#[derive(Clone, PartialEq, Debug)]
+pub enum Path {
+    Move(f32, f32),
+    Line(f32, f32),
+    Arc((f32, f32), f32, f32, f32),
+    Circle((f32, f32), f32),
+    Close,
+}
+
+// Fills treat every subpath, open or closed, as a simple polygon. Strokes join segments
+// with round joins.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum Ink {
+    Fill(Color),
+    Stroke(f32, Color),
+}
+
+// Stops are (position, color) pairs in ascending order with positions from 0 to 1. Linear
+// angles follow CSS: degrees clockwise from pointing up, with the gradient line sized so the
+// end stops meet the block's corners. Radial gradients take a center as fractions of the
+// block and a radius as a fraction of the distance to the farthest corner.
+#[derive(Clone, PartialEq, Debug)]
+pub enum Gradient {
+    Linear(f32, Vec<(f32, Color)>),
+    Radial((f32, f32), f32, Vec<(f32, Color)>),
+}
+
+impl Gradient {
+    pub fn stops(&self) -> &[(f32, Color)] {
+        match self {
+            &Gradient::Linear(_, ref stops) => stops,
+            &Gradient::Radial(_, _, ref stops) => stops,
+        }
+    }
+
+    pub fn color_at(&self, position: f32) -> Color {
+        let stops = self.stops();
+        match stops.iter().position(|&(stop, _)| position < stop) {
+            None => stops.last().map(|&(_, color)| color).unwrap_or_default(),
+            Some(0) => stops[0].1,
+            Some(next) => {
+                let ((start, a), (end, b)) = (stops[next - 1], stops[next]);
+                let f = if end > start { (position - start) / (end - start) } else { 1.0 };
+                Color::new(a.a + (b.a - a.a) * f, a.r + (b.r - a.r) * f, a.g + (b.g - a.g) * f, a.b + (b.b - a.b) * f)
+            }
+        }
+    }
+
+    pub fn is_opaque(&self) -> bool {
+        self.stops().iter().all(|&(_, color)| color.a >= 1.0)
+    }
+}
+
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum Fit {
+    Fill,
+    Contain,
+    Cover,
+    Center,
+}
+
+impl Default for Fit {
+    fn default() -> Self { Fit::Contain }
+}
+
+// Radii run clockwise from the top left and resolve against the shorter side of each block.
+#[derive(Clone, PartialEq, Debug)]
+pub enum Corners {
+    Uniform(Length),
+    Each(Length, Length, Length, Length),
+}
+
+impl Corners {
+    pub fn to_radii<'a>(&self, width: f32, height: f32, scribe: &Scribe<'a>) -> [f32; 4] {
+        let (short, long) = (width.min(height), width.max(height));
+        let radius = |length: &Length| length.to_f32(short, long, scribe).max(0.0).min(short / 2.0);
+        match self {
+            &Corners::Uniform(ref length) => [radius(length); 4],
+            &Corners::Each(ref top_left, ref top_right, ref bottom_right, ref bottom_left) => {
+                [radius(top_left), radius(top_right), radius(bottom_right), radius(bottom_left)]
+            }
+        }
+    }
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub enum Border {
+    Solid(Length, Color),
+}
+
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum Axis {
+    Horizontal,
+    Vertical,
+}
+
+// Rows and columns take the spacing between children and their cross-axis alignment.
+#[derive(Clone, PartialEq, Debug)]
+pub enum Stack {
+    Row(Length, Cross),
+    Column(Length, Cross),
+}
+
+impl Stack {
+    pub fn axis(&self) -> Axis {
+        match self {
+            &Stack::Row(_, _) => Axis::Horizontal,
+            &Stack::Column(_, _) => Axis::Vertical,
         }
     }
 }
 
+// Fill stretches each child across the stack. Place sizes each child to its content and
+// positions it within the stack's cross length.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum Cross {
+    Fill,
+    Place(Placement),
+}
+
+// A child's length along the stack. Fractions are of the length left after spacing and
+// weights share whatever the other children leave over.
+#[derive(Clone, PartialEq, Debug)]
+pub enum Span {
+    Fixed(Length),
+    Fraction(f32),
+    Weight(f32),
+    Intrinsic,
+}
+
+// Grids lay cells on column and row tracks, followed by the gaps between columns and
+// between rows. Tracks size like stack spans with Intrinsic sized to the cells that cover
+// only that track.
+#[derive(Clone, PartialEq, Debug)]
+pub enum Grid {
+    Tracks(Vec<Span>, Vec<Span>, Length, Length),
+}
+
+// A cell's column and row, how many tracks it covers in each direction and how the child
+// sits horizontally and vertically within the covered area.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct Cell {
+    pub column: usize,
+    pub row: usize,
+    pub column_span: usize,
+    pub row_span: usize,
+    pub align: (Cross, Cross),
+}
+
+impl Cell {
+    pub fn at(column: usize, row: usize) -> Self {
+        Cell { column, row, column_span: 1, row_span: 1, align: (Cross::Fill, Cross::Fill) }
+    }
+
+    pub fn span(self, column_span: usize, row_span: usize) -> Self {
+        Cell { column_span, row_span, ..self }
+    }
+
+    pub fn align(self, horizontal: Cross, vertical: Cross) -> Self {
+        Cell { align: (horizontal, vertical), ..self }
+    }
+}
+
+// Popups take a width and height resolved against the anchoring range. Sided popups open
+// on their preferred side of the anchor and flip to the opposite side when the window has
+// no room; they then slide along the anchor to stay inside the window. Centered popups
+// resolve their size against the window and sit in its middle.
+#[derive(Clone, PartialEq, Debug)]
+pub enum Popup {
+    Above(Length, Length),
+    Below(Length, Length),
+    Left(Length, Length),
+    Right(Length, Length),
+    Center(Length, Length),
+}
+
+impl Popup {
+    pub fn mirrored(&self) -> Self {
+        match self {
+            &Popup::Left(ref width, ref height) => Popup::Right(width.clone(), height.clone()),
+            &Popup::Right(ref width, ref height) => Popup::Left(width.clone(), height.clone()),
+            popup => popup.clone(),
+        }
+    }
+}
+
+// Sets the em that Length::Em and Length::LineHeight resolve against in a subtree. The
+// length resolves against the enclosing em, so Length::Full * 1.5 grows the text by half.
+#[derive(Clone, PartialEq, Debug)]
+pub enum TextSize {
+    Em(Length),
+}
+
+// Right-to-left lays a subtree out as left-to-right and then mirrors it across its range,
+// so horizontal positions, rows, columns and text placements all flip.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum Direction {
+    LeftToRight,
+    RightToLeft,
+}
+
+impl Default for Direction {
+    fn default() -> Self { Direction::LeftToRight }
+}
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum Fade {
+    Alpha(f32),
+}
+
+// Drifts move a subtree by horizontal and vertical lengths resolved against its range.
+// Only the subtree moves; the layout around it is unchanged.
+#[derive(Clone, PartialEq, Debug)]
+pub enum Drift {
+    By(Length, Length),
+}
+
+// Range clips every block in the subtree to the flood's own range.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum Clip {
+    Range,
+}
+
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum Shadow {
+    Clear,
+}
+
 #[derive(Clone, PartialEq, Debug)]
 pub enum Padding {
     Uniform(Length),
@@ -122,6 +411,108 @@ pub enum Padding {
     Horizontal(Length),
     Vertical(Length),
     Behind(Length),
+    Insets(Insets),
+    // As thick on every side as a Flood::Rim of the same length, which resolves against
+    // the short side of the range.
+    Rim(Length),
+}
+
+// Shrinks a child to a maximum width and height resolved against the parent range, then to
+// a width-to-height ratio, and places it horizontally and vertically in the space left.
+#[derive(Clone, PartialEq, Debug)]
+pub struct Confine {
+    pub ratio: Option<f32>,
+    pub max_width: Option<Length>,
+    pub max_height: Option<Length>,
+    pub placement: (Placement, Placement),
+}
+
+impl Confine {
+    pub fn ratio(ratio: f32) -> Self {
+        Confine { ratio: Some(ratio), ..Default::default() }
+    }
+
+    pub fn max_size(width: Length, height: Length) -> Self {
+        Confine { max_width: Some(width), max_height: Some(height), ..Default::default() }
+    }
+
+    pub fn place(self, horizontal: Placement, vertical: Placement) -> Self {
+        Confine { placement: (horizontal, vertical), ..self }
+    }
+
+    pub fn with_ratio(self, ratio: f32) -> Self {
+        Confine { ratio: Some(ratio), ..self }
+    }
+
+    pub fn size_in<'a>(&self, parent_width: f32, parent_height: f32, scribe: &Scribe<'a>) -> (f32, f32) {
+        let width = self.max_width.as_ref()
+            .map_or(parent_width, |length| length.to_f32(parent_width, parent_height, scribe).min(parent_width))
+            .max(0.0);
+        let height = self.max_height.as_ref()
+            .map_or(parent_height, |length| length.to_f32(parent_height, parent_width, scribe).min(parent_height))
+            .max(0.0);
+        match self.ratio {
+            Some(ratio) if ratio > 0.0 => {
+                if width > height * ratio {
+                    (height * ratio, height)
+                } else {
+                    (width, width / ratio)
+                }
+            }
+            _ => (width, height),
+        }
+    }
+
+    pub fn place_in<'a>(&self, range: &BlockRange, scribe: &Scribe<'a>) -> BlockRange {
+        let (width, height) = self.size_in(range.width, range.height, scribe);
+        let (h_placement, v_placement): (f32, f32) = (self.placement.0.into(), self.placement.1.into());
+        BlockRange {
+            left: range.left + (range.width - width) * h_placement,
+            top: range.top + (range.height - height) * v_placement,
+            width,
+            height,
+            approach: range.approach,
+        }
+    }
+}
+
+impl Default for Confine {
+    fn default() -> Self {
+        Confine { ratio: None, max_width: None, max_height: None, placement: (Placement::Center, Placement::Center) }
+    }
+}
+
+// Lengths in from the left, top, right and bottom sides of a range. Left and right resolve
+// against the range's width, top and bottom against its height.
+#[derive(Clone, PartialEq, Debug)]
+pub enum Insets {
+    Sides(Length, Length, Length, Length),
+}
+
+impl Insets {
+    pub fn uniform(length: Length) -> Self {
+        Insets::Sides(length.clone(), length.clone(), length.clone(), length)
+    }
+
+    pub fn dual(h_length: Length, v_length: Length) -> Self {
+        Insets::Sides(h_length.clone(), v_length.clone(), h_length, v_length)
+    }
+}
+
+impl Default for Insets {
+    fn default() -> Self {
+        Insets::uniform(Length::Zero)
+    }
+}
+
+// A box around content: the margin sits outside the background and border, and the
+// padding sits between the border and the content.
+#[derive(Clone, PartialEq, Debug, Default)]
+pub struct Frame {
+    pub margin: Insets,
+    pub border: Option<Border>,
+    pub background: Option<Color>,
+    pub padding: Insets,
 }
 
 #[derive(Clone, PartialEq, Debug)]
@@ -131,3 +522,48 @@ pub enum Position {
     Right(Length),
     Bottom(Length),
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn gradient_blends_between_stops_and_holds_past_the_ends() {
+        let gradient = Gradient::Linear(90.0, vec![(0.25, Color::black()), (0.75, Color::white())]);
+        assert_eq!(Color::black(), gradient.color_at(0.0));
+        assert_eq!(Color::custom_white(0.5), gradient.color_at(0.5));
+        assert_eq!(Color::white(), gradient.color_at(1.0));
+    }
+
+    #[test]
+    fn strata_stay_in_front_of_the_far_layer_and_below_the_overlay_plane() {
+        assert_eq!(5.0, Stratum::Elevation(1.0).add_to_range(2.0, 5.0));
+        assert_eq!(6.0, Stratum::Elevation(4.0).add_to_range(2.0, 5.0));
+        let elevated = Stratum::Elevation(30.0).add_to_range(2.0, 5.0);
+        assert!(elevated > 14.5 && elevated < OVERLAY_APPROACH);
+        let nested = (0..8).scan(OVERLAY_APPROACH - 4.0, |approach, _| {
+            *approach = Stratum::Elevation(10.0).add_to(*approach);
+            Some(*approach)
+        }).collect::<Vec<_>>();
+        assert!(nested.windows(2).all(|pair| pair[0] < pair[1] && pair[1] < OVERLAY_APPROACH));
+        let just_below = Stratum::JustBelow.add_to(OVERLAY_APPROACH - 0.001);
+        assert!(just_below > OVERLAY_APPROACH - 0.001 && just_below < OVERLAY_APPROACH);
+        assert_eq!(OVERLAY_APPROACH + 1.0 + 0.001, Stratum::JustBelow.add_to(OVERLAY_APPROACH + 1.0));
+        assert_eq!(OVERLAY_APPROACH, Stratum::Overlay.add_to(5.0));
+    }
+
+    #[test]
+    fn confined_range_keeps_ratio_within_max_size() {
+        let scribe = Scribe::default();
+        let range = BlockRange { left: 0.0, top: 0.0, width: 400.0, height: 300.0, approach: 0.0 };
+        let video = Confine::ratio(16.0 / 9.0).place_in(&range, &scribe);
+        assert_eq!((0.0, 37.5, 400.0, 225.0), (video.left, video.top, video.width, video.height));
+        let avatar = Confine::max_size(Length::Pixels(100.0), Length::Full)
+            .with_ratio(1.0)
+            .place(Placement::End, Placement::Start)
+            .place_in(&range, &scribe);
+        assert_eq!((300.0, 0.0, 100.0, 100.0), (avatar.left, avatar.top, avatar.width, avatar.height));
+        let banner = Confine::max_size(Length::Pixels(100.0), Length::Cross / 2).size_in(400.0, 300.0, &scribe);
+        assert_eq!((100.0, 200.0), banner);
+    }
+}
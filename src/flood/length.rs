@@ -13,6 +13,7 @@ pub enum Length {
     Third,
     Full,
     Min(Box<Length>, Box<Length>),
+    Max(Box<Length>, Box<Length>),
     Negative(Box<Length>),
     Cross,
     Inverse(Box<Length>),
@@ -20,6 +21,11 @@ pub enum Length {
     Text(String),
     TextUnit(String),
     CardApproach,
+    Dp(f32),
+    Em(f32),
+    LineHeight(f32),
+    WindowWidth(f32),
+    WindowHeight(f32),
 }
 
 impl Length {
@@ -35,6 +41,7 @@ impl Length {
             &Length::Sum(ref a, ref b) => a.to_f32(context, alt_context, scribe) + b.to_f32(context, alt_context, scribe),
             &Length::Scale(factor, ref a) => a.to_f32(context, alt_context, scribe) * factor,
             &Length::Min(ref a, ref b) => a.to_f32(context, alt_context, scribe).min(b.to_f32(context, alt_context, scribe)),
+            &Length::Max(ref a, ref b) => a.to_f32(context, alt_context, scribe).max(b.to_f32(context, alt_context, scribe)),
             &Length::Negative(ref a) => -a.to_f32(context, alt_context, scribe),
             &Length::Inverse(ref a) => 1.0 / a.to_f32(context, alt_context, scribe),
             &Length::Cross => alt_context,
@@ -42,11 +49,107 @@ impl Length {
             &Length::Text(ref text) => alt_context * scribe.size_text(text),
             &Length::TextUnit(ref text) => scribe.size_text(text),
             &Length::CardApproach => 2.0,
+            &Length::Dp(dp) => dp * scribe.dpi_factor(),
+            &Length::Em(ems) => ems * scribe.em(),
+            &Length::LineHeight(lines) => lines * scribe.line_height(),
+            &Length::WindowWidth(fraction) => fraction * scribe.window().0,
+            &Length::WindowHeight(fraction) => fraction * scribe.window().1,
+        }
+    }
+
+    // Folds constant subexpressions into pixels and drops identities so the length
+    // resolves in fewer steps. Lengths built with the arithmetic operators are folded as
+    // they are built.
+    pub fn simplify(self) -> Self {
+        match self {
+            Length::Sum(a, b) => Length::Sum(Box::new(a.simplify()), Box::new(b.simplify())).fold(),
+            Length::Scale(factor, a) => Length::Scale(factor, Box::new(a.simplify())).fold(),
+            Length::Min(a, b) => Length::Min(Box::new(a.simplify()), Box::new(b.simplify())).fold(),
+            Length::Max(a, b) => Length::Max(Box::new(a.simplify()), Box::new(b.simplify())).fold(),
+            Length::Negative(a) => Length::Negative(Box::new(a.simplify())).fold(),
+            Length::Inverse(a) => Length::Inverse(Box::new(a.simplify())).fold(),
+            Length::Product(a, b) => Length::Product(Box::new(a.simplify()), Box::new(b.simplify())).fold(),
+            length => length.fold(),
+        }
+    }
+
+    fn constant(&self) -> Option<f32> {
+        match self {
+            &Length::Zero => Some(0.0),
+            &Length::FingerTip => Some(44.0),
+            &Length::Pixels(pixels) => Some(pixels),
+            &Length::Spacing => Some(16.0),
+            &Length::CardApproach => Some(2.0),
+            _ => None,
+        }
+    }
+
+    // Folds the top of an expression whose operands are already folded.
+    fn fold(self) -> Self {
+        if let Some(pixels) = self.constant() {
+            return Length::Pixels(pixels);
+        }
+        match self {
+            Length::Sum(a, b) => match (a.constant(), b.constant()) {
+                (Some(a), Some(b)) => Length::Pixels(a + b),
+                (Some(a), None) if a == 0.0 => *b,
+                (None, Some(b)) if b == 0.0 => *a,
+                _ => Length::Sum(a, b),
+            },
+            Length::Scale(factor, a) => if let Some(pixels) = a.constant() {
+                Length::Pixels(pixels * factor)
+            } else if factor == 1.0 {
+                *a
+            } else {
+                match *a {
+                    Length::Scale(inner_factor, a) => Length::Scale(factor * inner_factor, a).fold(),
+                    a => Length::Scale(factor, Box::new(a)),
+                }
+            },
+            Length::Min(a, b) => match (a.constant(), b.constant()) {
+                (Some(a), Some(b)) => Length::Pixels(a.min(b)),
+                _ => Length::Min(a, b),
+            },
+            Length::Max(a, b) => match (a.constant(), b.constant()) {
+                (Some(a), Some(b)) => Length::Pixels(a.max(b)),
+                _ => Length::Max(a, b),
+            },
+            Length::Negative(a) => if let Some(pixels) = a.constant() {
+                Length::Pixels(-pixels)
+            } else {
+                match *a {
+                    Length::Negative(a) => *a,
+                    a => Length::Negative(Box::new(a)),
+                }
+            },
+            Length::Inverse(a) => if let Some(pixels) = a.constant() {
+                Length::Pixels(1.0 / pixels)
+            } else {
+                match *a {
+                    Length::Inverse(a) => *a,
+                    a => Length::Inverse(Box::new(a)),
+                }
+            },
+            Length::Product(a, b) => match (a.constant(), b.constant()) {
+                (Some(a), Some(b)) => Length::Pixels(a * b),
+                (Some(a), None) => Length::Scale(a, b).fold(),
+                (None, Some(b)) => Length::Scale(b, a).fold(),
+                _ => Length::Product(a, b),
+            },
+            length => length,
         }
     }
 
     pub fn min(self, rhs: Length) -> Self {
-        Length::Min(Box::new(self), Box::new(rhs))
+        Length::Min(Box::new(self), Box::new(rhs)).fold()
+    }
+
+    pub fn max(self, rhs: Length) -> Self {
+        Length::Max(Box::new(self), Box::new(rhs)).fold()
+    }
+
+    pub fn clamp(self, min: Length, max: Length) -> Self {
+        self.max(min).min(max)
     }
 }
 
@@ -54,7 +157,7 @@ impl Add for Length {
     type Output = Length;
 
     fn add(self, rhs: Length) -> <Self as Add<Length>>::Output {
-        Length::Sum(Box::new(self), Box::new(rhs))
+        Length::Sum(Box::new(self), Box::new(rhs)).fold()
     }
 }
 
@@ -62,7 +165,7 @@ impl Add<i32> for Length {
     type Output = Length;
 
     fn add(self, rhs: i32) -> <Self as Add<i32>>::Output {
-        Length::Sum(Box::new(self), Box::new(Length::Pixels(rhs as f32)))
+        self + Length::Pixels(rhs as f32)
     }
 }
 
@@ -70,7 +173,7 @@ impl Sub for Length {
     type Output = Length;
 
     fn sub(self, rhs: Length) -> <Self as Sub<Length>>::Output {
-        Length::Sum(Box::new(self), Box::new(Length::Negative(Box::new(rhs))))
+        self + Length::Negative(Box::new(rhs)).fold()
     }
 }
 
@@ -78,7 +181,7 @@ impl Mul for Length {
     type Output = Length;
 
     fn mul(self, rhs: Length) -> <Self as Mul<Length>>::Output {
-        Length::Product(Box::new(self), Box::new(rhs))
+        Length::Product(Box::new(self), Box::new(rhs)).fold()
     }
 }
 
@@ -95,7 +198,7 @@ impl Mul<f32> for Length {
     type Output = Length;
 
     fn mul(self, rhs: f32) -> <Self as Mul<f32>>::Output {
-        Length::Scale(rhs, Box::new(self))
+        Length::Scale(rhs, Box::new(self)).fold()
     }
 }
 
@@ -103,7 +206,7 @@ impl Div for Length {
     type Output = Length;
 
     fn div(self, rhs: Length) -> <Self as Div<Length>>::Output {
-        Length::Product(Box::new(self), Box::new(Length::Inverse(Box::new(rhs))))
+        self * Length::Inverse(Box::new(rhs)).fold()
     }
 }
 
@@ -138,3 +241,32 @@ impl Div<f32> for Length {
         self * rhs.recip()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn constant_lengths_fold_into_pixels() {
+        assert_eq!(Length::Pixels(40.0), Length::Spacing * 2 + Length::Pixels(8.0));
+        assert_eq!(Length::Pixels(4.0), Length::Spacing / 4);
+        assert_eq!(Length::Scale(0.25, Box::new(Length::Full)), Length::Full * 0.5 / 2);
+        assert_eq!(Length::Full, Length::Full + Length::Zero);
+        let cross = (Length::Cross - Length::Spacing) * Length::Pixels(0.5);
+        assert_eq!(Length::Scale(0.5, Box::new(Length::Sum(Box::new(Length::Cross), Box::new(Length::Pixels(-16.0))))), cross);
+    }
+
+    #[test]
+    fn relative_units_follow_the_scribe() {
+        let mut scribe = Scribe::default();
+        scribe.set_dpi_factor(2.0);
+        scribe.set_window(400.0, 300.0);
+        assert_eq!(32.0, Length::Dp(16.0).to_f32(0.0, 0.0, &scribe));
+        assert_eq!(8.0, Length::Em(0.5).to_f32(0.0, 0.0, &scribe));
+        assert_eq!(24.0, scribe.with_em(12.0, || Length::Em(2.0).to_f32(0.0, 0.0, &scribe)));
+        assert_eq!(100.0, Length::WindowWidth(0.25).to_f32(0.0, 0.0, &scribe));
+        assert_eq!(150.0, (Length::WindowHeight(1.0) * 0.5).to_f32(0.0, 0.0, &scribe));
+        assert_eq!(20.0, Length::Full.clamp(Length::Pixels(20.0), Length::Pixels(80.0)).to_f32(10.0, 0.0, &scribe));
+        assert_eq!(80.0, Length::Full.clamp(Length::Pixels(20.0), Length::Pixels(80.0)).to_f32(90.0, 0.0, &scribe));
+    }
+}
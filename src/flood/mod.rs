@@ -1,10 +1,11 @@
-use Color;
+use {Color, ImageSource};
 pub use self::extras::*;
 pub use self::length::Length;
 pub use self::signal::Signal;
 pub use self::version::Version;
 pub use self::version::Counter as VersionCounter;
 use std::ops::Add;
+use std::sync::Arc;
 
 mod length;
 mod extras;
@@ -12,12 +13,8 @@ mod signal;
 mod version;
 
 pub fn bar<MsgT: Clone>(segments: Vec<Flood<MsgT>>) -> Flood<MsgT> {
-    let enumeration = segments.into_iter().enumerate().collect::<Vec<_>>();
-    enumeration.into_iter().fold(
-        Flood::Color(Color::default()),
-        |bar, (i, segment)| {
-            bar + (Position::Right(Length::Full / (i as u32 + 1)), segment)
-        })
+    let children = segments.into_iter().map(|segment| (Span::Weight(1.0), segment)).collect();
+    Flood::Stream(Stack::Row(Length::Zero, Cross::Fill), children) + Flood::Color(Color::default())
 }
 
 #[derive(Clone, Debug)]
@@ -25,12 +22,27 @@ pub enum Flood<MsgT> where
     MsgT: Clone
 {
     Color(Color),
+    Gradient(Gradient),
     Text(String, Color, Placement),
+    Image(ImageSource, Fit, Color),
+    Canvas(Vec<Path>, Vec<Ink>),
     Barrier(Position, Box<Flood<MsgT>>, Box<Flood<MsgT>>),
     Vessel(Padding, Box<Flood<MsgT>>),
     Sediment(Stratum, Box<Flood<MsgT>>, Box<Flood<MsgT>>),
     Ripple(Sensor<MsgT>, Box<Flood<MsgT>>),
     Escape(Raft<MsgT>),
+    Shade(Shadow, Box<Flood<MsgT>>),
+    Clip(Clip, Box<Flood<MsgT>>),
+    Fade(Fade, Box<Flood<MsgT>>),
+    Drift(Drift, Box<Flood<MsgT>>),
+    Float(Popup, Box<Flood<MsgT>>, Box<Flood<MsgT>>),
+    Stream(Stack, Vec<(Span, Flood<MsgT>)>),
+    Grid(Grid, Vec<(Cell, Flood<MsgT>)>),
+    Round(Corners, Box<Flood<MsgT>>),
+    Rim(Border, Box<Flood<MsgT>>),
+    TextSize(TextSize, Box<Flood<MsgT>>),
+    Confine(Confine, Box<Flood<MsgT>>),
+    Direction(Direction, Box<Flood<MsgT>>),
 }
 
 impl<MsgT> Default for Flood<MsgT> where
@@ -41,6 +53,52 @@ impl<MsgT> Default for Flood<MsgT> where
     }
 }
 
+impl<MsgT> Flood<MsgT> where
+    MsgT: Clone + 'static
+{
+    // Converts the messages of every sensor and raft in the tree so a flood drawn for a
+    // child's messages can sit inside a flood for its parent's messages.
+    pub fn map<NewMsgT, F>(self, f: F) -> Flood<NewMsgT> where
+        NewMsgT: Clone + 'static,
+        F: Fn(MsgT) -> NewMsgT + Send + Sync + 'static
+    {
+        let f: Arc<Fn(MsgT) -> NewMsgT + Send + Sync> = Arc::new(f);
+        self.map_arc(&f)
+    }
+
+    fn map_arc<NewMsgT>(self, f: &Arc<Fn(MsgT) -> NewMsgT + Send + Sync>) -> Flood<NewMsgT> where
+        NewMsgT: Clone + 'static
+    {
+        let map_box = |flood: Box<Flood<MsgT>>| Box::new(flood.map_arc::<NewMsgT>(f));
+        match self {
+            Flood::Color(color) => Flood::Color(color),
+            Flood::Gradient(gradient) => Flood::Gradient(gradient),
+            Flood::Text(text, color, placement) => Flood::Text(text, color, placement),
+            Flood::Image(source, fit, tint) => Flood::Image(source, fit, tint),
+            Flood::Canvas(paths, inks) => Flood::Canvas(paths, inks),
+            Flood::Barrier(position, a, b) => Flood::Barrier(position, map_box(a), map_box(b)),
+            Flood::Vessel(padding, flood) => Flood::Vessel(padding, map_box(flood)),
+            Flood::Sediment(stratum, far, near) => Flood::Sediment(stratum, map_box(far), map_box(near)),
+            Flood::Ripple(sensor, flood) => Flood::Ripple(sensor.map(f.clone()), map_box(flood)),
+            Flood::Escape(raft) => Flood::Escape(raft.map(f.clone())),
+            Flood::Shade(shadow, flood) => Flood::Shade(shadow, map_box(flood)),
+            Flood::Clip(clip, flood) => Flood::Clip(clip, map_box(flood)),
+            Flood::Fade(fade, flood) => Flood::Fade(fade, map_box(flood)),
+            Flood::Drift(drift, flood) => Flood::Drift(drift, map_box(flood)),
+            Flood::Float(popup, anchor, flood) => Flood::Float(popup, map_box(anchor), map_box(flood)),
+            Flood::Stream(stack, children) => {
+                Flood::Stream(stack, children.into_iter().map(|(span, child)| (span, child.map_arc(f))).collect())
+            }
+            Flood::Grid(grid, cells) => Flood::Grid(grid, cells.into_iter().map(|(cell, child)| (cell, child.map_arc(f))).collect()),
+            Flood::Round(corners, flood) => Flood::Round(corners, map_box(flood)),
+            Flood::Rim(border, flood) => Flood::Rim(border, map_box(flood)),
+            Flood::TextSize(text_size, flood) => Flood::TextSize(text_size, map_box(flood)),
+            Flood::Confine(confine, flood) => Flood::Confine(confine, map_box(flood)),
+            Flood::Direction(direction, flood) => Flood::Direction(direction, map_box(flood)),
+        }
+    }
+}
+
 impl<MsgT> Add<Sensor<MsgT>> for Flood<MsgT> where
     MsgT: Clone
 {
@@ -61,6 +119,131 @@ impl<MsgT> Add<Padding> for Flood<MsgT> where
     }
 }
 
+impl<MsgT> Add<Shadow> for Flood<MsgT> where
+    MsgT: Clone
+{
+    type Output = Flood<MsgT>;
+
+    fn add(self, rhs: Shadow) -> <Self as Add<Shadow>>::Output {
+        Flood::Shade(rhs, Box::new(self))
+    }
+}
+
+impl<MsgT> Add<Clip> for Flood<MsgT> where
+    MsgT: Clone
+{
+    type Output = Flood<MsgT>;
+
+    fn add(self, rhs: Clip) -> <Self as Add<Clip>>::Output {
+        Flood::Clip(rhs, Box::new(self))
+    }
+}
+
+impl<MsgT> Add<Fade> for Flood<MsgT> where
+    MsgT: Clone
+{
+    type Output = Flood<MsgT>;
+
+    fn add(self, rhs: Fade) -> <Self as Add<Fade>>::Output {
+        Flood::Fade(rhs, Box::new(self))
+    }
+}
+
+impl<MsgT> Add<Drift> for Flood<MsgT> where
+    MsgT: Clone
+{
+    type Output = Flood<MsgT>;
+
+    fn add(self, rhs: Drift) -> <Self as Add<Drift>>::Output {
+        Flood::Drift(rhs, Box::new(self))
+    }
+}
+
+impl<MsgT> Add<Corners> for Flood<MsgT> where
+    MsgT: Clone
+{
+    type Output = Flood<MsgT>;
+
+    fn add(self, rhs: Corners) -> <Self as Add<Corners>>::Output {
+        Flood::Round(rhs, Box::new(self))
+    }
+}
+
+impl<MsgT> Add<Border> for Flood<MsgT> where
+    MsgT: Clone
+{
+    type Output = Flood<MsgT>;
+
+    fn add(self, rhs: Border) -> <Self as Add<Border>>::Output {
+        Flood::Rim(rhs, Box::new(self))
+    }
+}
+
+impl<MsgT> Add<Insets> for Flood<MsgT> where
+    MsgT: Clone
+{
+    type Output = Flood<MsgT>;
+
+    fn add(self, rhs: Insets) -> <Self as Add<Insets>>::Output {
+        Flood::Vessel(Padding::Insets(rhs), Box::new(self))
+    }
+}
+
+impl<MsgT> Add<Frame> for Flood<MsgT> where
+    MsgT: Clone
+{
+    type Output = Flood<MsgT>;
+
+    fn add(self, rhs: Frame) -> <Self as Add<Frame>>::Output {
+        let content = match rhs.border {
+            Some(Border::Solid(ref length, _)) => self + rhs.padding + Padding::Rim(length.clone()),
+            None => self + rhs.padding,
+        };
+        let framed = match (rhs.background, rhs.border) {
+            (None, None) => content,
+            (background, border) => {
+                let backing = Flood::Color(background.unwrap_or(Color::new(0.0, 0.0, 0.0, 0.0)));
+                let backing = match border {
+                    Some(border) => backing + border,
+                    None => backing,
+                };
+                content + (Stratum::JustBelow, backing)
+            }
+        };
+        framed + rhs.margin
+    }
+}
+
+impl<MsgT> Add<Confine> for Flood<MsgT> where
+    MsgT: Clone
+{
+    type Output = Flood<MsgT>;
+
+    fn add(self, rhs: Confine) -> <Self as Add<Confine>>::Output {
+        Flood::Confine(rhs, Box::new(self))
+    }
+}
+
+impl<MsgT> Add<Direction> for Flood<MsgT> where
+    MsgT: Clone
+{
+    type Output = Flood<MsgT>;
+
+    fn add(self, rhs: Direction) -> <Self as Add<Direction>>::Output {
+        Flood::Direction(rhs, Box::new(self))
+    }
+}
+
+impl<MsgT> Add<TextSize> for Flood<MsgT> where
+    MsgT: Clone
+{
+    type Output = Flood<MsgT>;
+
+    fn add(self, rhs: TextSize) -> <Self as Add<TextSize>>::Output {
+        Flood::TextSize(rhs, Box::new(self))
+    }
+}
+
 impl<MsgT> Add<(Stratum, Flood<MsgT>)> for Flood<MsgT> where
     MsgT: Clone
 {
@@ -81,6 +264,16 @@ impl<MsgT> Add<Flood<MsgT>> for Flood<MsgT> where
     }
 }
 
+impl<MsgT> Add<(Popup, Flood<MsgT>)> for Flood<MsgT> where
+    MsgT: Clone
+{
+    type Output = Flood<MsgT>;
+
+    fn add(self, (popup, flood): (Popup, Flood<MsgT>)) -> <Self as Add<(Popup, Flood<MsgT>)>>::Output {
+        Flood::Float(popup, Box::new(self), Box::new(flood))
+    }
+}
+
 impl<MsgT> Add<(Position, Flood<MsgT>)> for Flood<MsgT> where
     MsgT: Clone
 {
@@ -90,3 +283,34 @@ impl<MsgT> Add<(Position, Flood<MsgT>)> for Flood<MsgT> where
         Flood::Barrier(position, Box::new(self), Box::new(flood))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use ::TouchMsg;
+    use super::*;
+
+    #[derive(Clone, PartialEq, Debug)]
+    enum ChildMsg {
+        Touched(u64),
+    }
+
+    #[derive(Clone, PartialEq, Debug)]
+    enum ParentMsg {
+        Child(ChildMsg),
+    }
+
+    #[test]
+    fn map_wraps_sensor_messages() {
+        let sensor = Sensor::Touch(7, Arc::new(|touch_msg: TouchMsg| ChildMsg::Touched(touch_msg.tag())));
+        let child = Flood::Color(Color::white()) + sensor + Padding::Uniform(Length::Spacing);
+        match child.map(ParentMsg::Child) {
+            Flood::Vessel(_, flood) => match *flood {
+                Flood::Ripple(Sensor::Touch(tag, adapter), _) => {
+                    assert_eq!(ParentMsg::Child(ChildMsg::Touched(7)), adapter(TouchMsg::Cancel(tag)));
+                }
+                flood => panic!("unexpected {:?}", flood),
+            },
+            flood => panic!("unexpected {:?}", flood),
+        }
+    }
+}
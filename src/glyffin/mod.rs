@@ -1,40 +1,110 @@
-use arrayvec;
 use arrayvec::ArrayVec;
 use glium;
 use glium::backend::Facade;
-use rusttype::{point, Rect};
+use rendering::{merge_approaches, sort_by_approach, Layers};
+use rusttype::{point, PositionedGlyph, Rect};
 use rusttype::gpu_cache::Cache;
 use std::borrow::Cow;
-use scribe::{Scribe, Scale};
+use std::collections::HashMap;
+use std::mem;
+use scribe::{GlyphSpot, Scribe, Scale};
+use self::sdf_atlas::SdfAtlas;
 
-pub struct QuipRenderer<'a> {
-    pub program: glium::Program,
-    pub cache: Cache,
-    pub cache_dimensions: (u32, u32),
+mod sdf_atlas;
+
+const MAX_ATLAS_SIDE: u32 = 4096;
+// Quips at or above this pixel scale are drawn from distance fields instead of the glyph cache.
+const SDF_MIN_SCALE: f32 = 24.0;
+
+pub struct Quip<'b> {
+    pub text: &'b str,
+    pub anchor: (f32, f32),
+    pub scale: Scale,
+    pub width: i32,
+    pub height: i32,
+    pub approach: f32,
+    pub colour: [f32; 4],
+    pub placement: f32,
+    pub clip: [f32; 4],
+}
+
+struct LaidQuip<'q, 'f> {
+    quip: &'q Quip<'q>,
+    glyphs: Vec<PositionedGlyph<'f>>,
+}
+
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+struct LayoutKey {
+    text: String,
+    scale: (u32, u32),
+    width: i32,
+    height: i32,
+    placement: u32,
+    colour: [u32; 4],
+}
+
+impl LayoutKey {
+    fn new(quip: &Quip) -> Self {
+        let (scale, colour) = (quip.scale, quip.colour);
+        LayoutKey {
+            text: quip.text.to_owned(),
+            scale: (scale.x.to_bits(), scale.y.to_bits()),
+            width: quip.width,
+            height: quip.height,
+            placement: quip.placement.to_bits(),
+            colour: [colour[0].to_bits(), colour[1].to_bits(), colour[2].to_bits(), colour[3].to_bits()],
+        }
+    }
+}
+
+struct GlyphAtlas {
+    cache: Cache,
     texture: glium::texture::Texture2d,
-    modelview: [[f32; 4]; 4],
+    dimensions: (u32, u32),
     vertex_buffer: glium::VertexBuffer<Vertex>,
-    draw_parameters: glium::DrawParameters<'static>,
-    scribe: Scribe<'a>,
+    layers: Layers,
 }
 
-impl<'a> QuipRenderer<'a> {
-    pub fn set_modelview(&mut self, modelview: [[f32; 4]; 4]) {
-        self.modelview = modelview;
+impl GlyphAtlas {
+    fn new<F: Facade>((width, height): (u32, u32), display: &F) -> Self {
+        let texture = glium::texture::Texture2d::with_format(
+            display,
+            glium::texture::RawImage2d {
+                data: Cow::Owned(vec![128u8; width as usize * height as usize]),
+                width,
+                height,
+                format: glium::texture::ClientFormat::U8,
+            },
+            glium::texture::UncompressedFloatFormat::U8,
+            glium::texture::MipmapsOption::NoMipmap,
+        ).unwrap();
+        GlyphAtlas {
+            cache: Cache::new(width, height, 0.1, 0.1),
+            texture,
+            dimensions: (width, height),
+            vertex_buffer: glium::VertexBuffer::new(display, &[]).unwrap(),
+            layers: Layers::default(),
+        }
+    }
+
+    fn can_grow(&self) -> bool {
+        let (width, height) = self.dimensions;
+        width < MAX_ATLAS_SIDE || height < MAX_ATLAS_SIDE
+    }
+
+    fn grow<F: Facade>(&mut self, display: &F) {
+        let (width, height) = self.dimensions;
+        *self = GlyphAtlas::new(((width * 2).min(MAX_ATLAS_SIDE), (height * 2).min(MAX_ATLAS_SIDE)), display);
     }
 
-    pub fn layout_paragraph<F: Facade>(&mut self, text: &str, (x, y): (f32, f32), scale: Scale, width: i32, z: f32, colour: [f32; 4], placement: f32, display: &F) {
-        const MAX_SCALE_LIMIT: f32 = 512.0;  // Do determine this dynamically and align text when limit is hit.
-        let scale = Scale {
-            x: scale.x.min(MAX_SCALE_LIMIT),
-            y: scale.y.min(MAX_SCALE_LIMIT),
-        };
-        let glyphs = self.scribe.fit_text(text, scale, width, placement);
-        for glyph in &glyphs {
-            self.cache.queue_glyph(0, glyph.clone());
+    fn cache_quips(&mut self, laid_quips: &[LaidQuip], members: &[usize]) -> bool {
+        for &index in members {
+            for glyph in &laid_quips[index].glyphs {
+                self.cache.queue_glyph(0, glyph.clone());
+            }
         }
         let texture = &self.texture;
-        self.cache.cache_queued(|rect, data| {
+        let cached = self.cache.cache_queued(|rect, data| {
             texture.main_level().write(glium::Rect {
                 left: rect.min.x,
                 bottom: rect.min.y,
@@ -46,104 +116,247 @@ impl<'a> QuipRenderer<'a> {
                 height: rect.height(),
                 format: glium::texture::ClientFormat::U8,
             });
-        }).expect("cache_queued");
-
-        implement_vertex!(Vertex, position, tex_coords, colour);
-        let (x, y) = (x.round(), y.round());
-        let vertices: Vec<Vertex> = glyphs.iter()
-            .flat_map(|g| if let Ok(Some((uv_rect, screen_rect))) = self.cache.rect_for(0, g) {
-                let gl_rect = Rect {
-                    min: point(x + screen_rect.min.x as f32, y + screen_rect.min.y as f32),
-                    max: point(x + screen_rect.max.x as f32, y + screen_rect.max.y as f32),
-                };
-                layout_vertices(z, &uv_rect, &gl_rect, &colour)
-            } else {
-                arrayvec::ArrayVec::new()
-            }).collect();
+        }).is_ok();
+        if !cached {
+            // Start from an empty cache so a failed attempt leaves nothing queued or half-packed.
+            let (width, height) = self.dimensions;
+            self.cache = Cache::new(width, height, 0.1, 0.1);
+        }
+        cached
+    }
+
+    fn set_quips<F: Facade>(&mut self, laid_quips: &[LaidQuip], members: &[usize], display: &F) {
+        let mut vertices: Vec<Vertex> = Vec::new();
+        let mut layers = Layers::default();
+        for index in by_approach(laid_quips, members) {
+            let LaidQuip { quip, ref glyphs } = laid_quips[index];
+            let (x, y) = quip.anchor;
+            let (x, y) = (x.round(), y.round());
+            let start = vertices.len();
+            for glyph in glyphs {
+                if let Ok(Some((uv_rect, screen_rect))) = self.cache.rect_for(0, glyph) {
+                    let gl_rect = Rect {
+                        min: point(x + screen_rect.min.x as f32, y + screen_rect.min.y as f32),
+                        max: point(x + screen_rect.max.x as f32, y + screen_rect.max.y as f32),
+                    };
+                    vertices.extend(layout_vertices(quip.approach, &uv_rect, &gl_rect, &quip.colour, quip.clip));
+                }
+            }
+            layers.push(quip.approach, vertices.len() - start);
+        }
         self.vertex_buffer = glium::VertexBuffer::new(display, &vertices).expect("VertexBuffer::new");
+        self.layers = layers;
+    }
+}
+
+pub struct QuipRenderer<'a> {
+    pub program: glium::Program,
+    pub sdf_program: glium::Program,
+    atlases: Vec<GlyphAtlas>,
+    sdf_atlas: SdfAtlas,
+    modelview: [[f32; 4]; 4],
+    draw_parameters: glium::DrawParameters<'static>,
+    layouts: HashMap<LayoutKey, Vec<GlyphSpot>>,
+    scribe: Scribe<'a>,
+}
+
+impl<'a> QuipRenderer<'a> {
+    pub fn set_modelview(&mut self, modelview: [[f32; 4]; 4]) {
+        self.modelview = modelview;
     }
 
-    pub fn draw(&self, frame: &mut glium::Frame) {
+    pub fn set_quips<F: Facade>(&mut self, quips: &[Quip], display: &F) {
+        // Layouts not used by these quips are dropped so the cache follows the screen.
+        let mut stale_layouts = mem::replace(&mut self.layouts, HashMap::new());
+        let layouts = &mut self.layouts;
+        let scribe = &self.scribe;
+        let laid_quips = quips.iter()
+            .map(|quip| {
+                let key = LayoutKey::new(quip);
+                let cached_spots = layouts.get(&key).cloned();
+                let spots = match cached_spots {
+                    Some(spots) => spots,
+                    None => {
+                        let spots = match stale_layouts.remove(&key) {
+                            Some(spots) => spots,
+                            None => scribe.fit_spots(quip.text, quip.scale, quip.width, quip.height, quip.placement),
+                        };
+                        layouts.insert(key, spots.clone());
+                        spots
+                    }
+                };
+                let glyphs = scribe.position_spots(&spots).into_iter()
+                    .filter(|glyph| is_sdf_quip(quip) || fits_atlas(glyph))
+                    .collect();
+                LaidQuip { quip, glyphs }
+            })
+            .collect::<Vec<_>>();
+
+        let (sdf_members, cache_members): (Vec<usize>, Vec<usize>) = (0..laid_quips.len())
+            .partition(|&index| is_sdf_quip(laid_quips[index].quip));
+        self.sdf_atlas.set_quips(scribe.font(), &laid_quips, &sdf_members, display);
+
+        let pages = page_quips(&mut self.atlases, &laid_quips, cache_members, display);
+        self.atlases.truncate(pages.len().max(1));
+        for (atlas, members) in self.atlases.iter_mut().zip(pages.iter()) {
+            atlas.set_quips(&laid_quips, members, display);
+        }
+        if pages.is_empty() {
+            self.atlases[0].set_quips(&laid_quips, &[], display);
+        }
+    }
+
+    pub fn approaches(&self) -> Vec<f32> {
+        let mut approaches = self.atlases.iter().map(|atlas| atlas.layers.approaches()).collect::<Vec<_>>();
+        approaches.push(self.sdf_atlas.approaches());
+        merge_approaches(approaches)
+    }
+
+    // Text blends at its edges, so it is drawn one approach at a time with everything else
+    // that blends.
+    pub fn draw(&self, frame: &mut glium::Frame, approach: f32) {
         use glium::Surface;
-        let sampler = self.texture.sampled().magnify_filter(glium::uniforms::MagnifySamplerFilter::Nearest);
-        let uniforms = uniform! { tex: sampler, modelview: self.modelview };
-        frame.draw(&self.vertex_buffer,
-                   glium::index::NoIndices(glium::index::PrimitiveType::TrianglesList),
-                   &self.program,
-                   &uniforms,
-                   &self.draw_parameters)
-            .expect("frame.draw");
+        for atlas in &self.atlases {
+            let range = match atlas.layers.range(approach) {
+                Some(range) => range,
+                None => continue,
+            };
+            let sampler = atlas.texture.sampled().magnify_filter(glium::uniforms::MagnifySamplerFilter::Nearest);
+            let uniforms = uniform! { tex: sampler, modelview: self.modelview };
+            frame.draw(atlas.vertex_buffer.slice(range).unwrap(),
+                       glium::index::NoIndices(glium::index::PrimitiveType::TrianglesList),
+                       &self.program,
+                       &uniforms,
+                       &self.draw_parameters)
+                .expect("frame.draw");
+        }
+        self.sdf_atlas.draw(frame, approach, &self.sdf_program, self.modelview, &self.draw_parameters);
     }
 
     pub fn new<F: Facade>(cache_dpi_factor: f32, modelview: [[f32; 4]; 4], display: &F) -> Self {
         let (vertex_shader, fragment_shader) = (include_str!("quip_vertex_shader.glsl"),
                                                 include_str!("quip_fragment_shader.glsl"));
         let program = program!(display, 140 => {vertex: vertex_shader, fragment: fragment_shader}).unwrap();
-        let (cache_width, cache_height) = (512 * cache_dpi_factor as u32, 512 * cache_dpi_factor as u32);
-        let texture = glium::texture::Texture2d::with_format(
-            display,
-            glium::texture::RawImage2d {
-                data: Cow::Owned(vec![128u8; cache_width as usize * cache_height as usize]),
-                width: cache_width,
-                height: cache_height,
-                format: glium::texture::ClientFormat::U8,
-            },
-            glium::texture::UncompressedFloatFormat::U8,
-            glium::texture::MipmapsOption::NoMipmap,
-        ).unwrap();
+        let sdf_fragment_shader = include_str!("quip_sdf_fragment_shader.glsl");
+        let sdf_program = program!(display, 140 => {vertex: vertex_shader, fragment: sdf_fragment_shader}).unwrap();
+        let cache_dimensions = (512 * cache_dpi_factor as u32, 512 * cache_dpi_factor as u32);
         QuipRenderer {
             program,
-            cache: Cache::new(cache_width, cache_height, 0.1, 0.1),
-            cache_dimensions: (cache_width, cache_height),
-            texture,
+            sdf_program,
+            atlases: vec![GlyphAtlas::new(cache_dimensions, display)],
+            sdf_atlas: SdfAtlas::new(),
             modelview,
-            vertex_buffer: glium::VertexBuffer::new(display, &[]).unwrap(),
             draw_parameters: glium::DrawParameters {
                 depth: glium::Depth {
-                    test: glium::DepthTest::IfLess,
-                    write: true,
+                    test: glium::DepthTest::IfLessOrEqual,
+                    write: false,
                     ..Default::default()
                 },
                 blend: glium::Blend::alpha_blending(),
                 smooth: Some(glium::Smooth::Nicest),
                 ..Default::default()
             },
+            layouts: HashMap::new(),
             scribe: Scribe::default(),
         }
     }
 }
 
-fn layout_vertices(z: f32, uv_rect: &Rect<f32>, gl_rect: &Rect<f32>, colour: &[f32; 4]) -> ArrayVec<[Vertex; 6]> {
+// Members sorted back to front, keeping their order among quips at the same approach.
+fn by_approach(laid_quips: &[LaidQuip], members: &[usize]) -> Vec<usize> {
+    let mut members = members.to_vec();
+    sort_by_approach(&mut members, |&index| laid_quips[index].quip.approach);
+    members
+}
+
+fn is_sdf_quip(quip: &Quip) -> bool {
+    quip.scale.y >= SDF_MIN_SCALE
+}
+
+fn fits_atlas(glyph: &PositionedGlyph) -> bool {
+    match glyph.pixel_bounding_box() {
+        Some(bounding_box) => (bounding_box.width() as u32) < MAX_ATLAS_SIDE && (bounding_box.height() as u32) < MAX_ATLAS_SIDE,
+        None => true,
+    }
+}
+
+// Assigns quips to atlas pages in order. A page grows until it reaches MAX_ATLAS_SIDE,
+// then takes the longest run of quips that fits and leaves the rest for the next page.
+fn page_quips<F: Facade>(atlases: &mut Vec<GlyphAtlas>, laid_quips: &[LaidQuip], members: Vec<usize>, display: &F) -> Vec<Vec<usize>> {
+    let mut pages = Vec::new();
+    let mut remaining = members;
+    while !remaining.is_empty() {
+        if atlases.len() == pages.len() {
+            let dimensions = atlases[0].dimensions;
+            atlases.push(GlyphAtlas::new(dimensions, display));
+        }
+        let atlas = &mut atlases[pages.len()];
+        if atlas.cache_quips(laid_quips, &remaining) {
+            pages.push(remaining);
+            break;
+        }
+        if atlas.can_grow() {
+            atlas.grow(display);
+            continue;
+        }
+        let (mut fits, mut overflows) = (0, remaining.len());
+        while overflows - fits > 1 {
+            let middle = (fits + overflows) / 2;
+            if atlas.cache_quips(laid_quips, &remaining[..middle]) {
+                fits = middle;
+            } else {
+                overflows = middle;
+            }
+        }
+        if fits == 0 || !atlas.cache_quips(laid_quips, &remaining[..fits]) {
+            println!("Dropped quip too large for a glyph atlas: {:?}", laid_quips[remaining[0]].quip.text);
+            remaining.remove(0);
+            continue;
+        }
+        let rest = remaining.split_off(fits);
+        pages.push(remaining);
+        remaining = rest;
+    }
+    pages
+}
+
+fn layout_vertices(z: f32, uv_rect: &Rect<f32>, gl_rect: &Rect<f32>, colour: &[f32; 4], clip: [f32; 4]) -> ArrayVec<[Vertex; 6]> {
     ArrayVec::<[Vertex; 6]>::from([
         Vertex {
             position: [gl_rect.min.x, gl_rect.max.y, z],
             tex_coords: [uv_rect.min.x, uv_rect.max.y],
             colour: *colour,
+            clip,
         },
         Vertex {
             position: [gl_rect.min.x, gl_rect.min.y, z],
             tex_coords: [uv_rect.min.x, uv_rect.min.y],
             colour: *colour,
+            clip,
         },
         Vertex {
             position: [gl_rect.max.x, gl_rect.min.y, z],
             tex_coords: [uv_rect.max.x, uv_rect.min.y],
             colour: *colour,
+            clip,
         },
         Vertex {
             position: [gl_rect.max.x, gl_rect.min.y, z],
             tex_coords: [uv_rect.max.x, uv_rect.min.y],
             colour: *colour,
+            clip,
         },
         Vertex {
             position: [gl_rect.max.x, gl_rect.max.y, z],
             tex_coords: [uv_rect.max.x, uv_rect.max.y],
             colour: *colour,
+            clip,
         },
         Vertex {
             position: [gl_rect.min.x, gl_rect.max.y, z],
             tex_coords: [uv_rect.min.x, uv_rect.max.y],
             colour: *colour,
+            clip,
         }])
 }
 
@@ -152,4 +365,6 @@ pub struct Vertex {
     position: [f32; 3],
     tex_coords: [f32; 2],
     colour: [f32; 4],
+    clip: [f32; 4],
 }
+implement_vertex!(Vertex, position, tex_coords, colour, clip);
@@ -1,8 +1,13 @@
 #version 140
 uniform sampler2D tex;
 in vec2 v_tex_coords;
+in vec2 v_point;
 in vec4 v_colour;
+in vec4 v_clip;
 out vec4 f_colour;
 void main() {
+    if (v_point.x < v_clip.x || v_point.y < v_clip.y || v_point.x >= v_clip.z || v_point.y >= v_clip.w) discard;
     f_colour = v_colour * vec4(1.0, 1.0, 1.0, texture(tex, v_tex_coords).r);
+    // Keep empty glyph margins out of the depth buffer so they hide nothing drawn later.
+    if (f_colour.a < 0.01) discard;
 }
@@ -0,0 +1,15 @@
+#version 140
+uniform sampler2D tex;
+in vec2 v_tex_coords;
+in vec2 v_point;
+in vec4 v_colour;
+in vec4 v_clip;
+out vec4 f_colour;
+void main() {
+    float distance = texture(tex, v_tex_coords).r;
+    float smoothing = 0.7 * fwidth(distance);
+    float alpha = smoothstep(0.5 - smoothing, 0.5 + smoothing, distance);
+    f_colour = vec4(v_colour.rgb, v_colour.a * alpha);
+    if (v_point.x < v_clip.x || v_point.y < v_clip.y || v_point.x >= v_clip.z || v_point.y >= v_clip.w) discard;
+    if (f_colour.a < 0.01) discard;
+}
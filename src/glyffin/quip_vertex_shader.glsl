@@ -3,10 +3,15 @@ uniform mat4 modelview;
 in vec3 position;
 in vec2 tex_coords;
 in vec4 colour;
+in vec4 clip;
+out vec2 v_point;
 out vec2 v_tex_coords;
 out vec4 v_colour;
+out vec4 v_clip;
 void main() {
     gl_Position = modelview * vec4(position, 1.0);
+    v_point = position.xy;
     v_tex_coords = tex_coords;
     v_colour = colour;
+    v_clip = clip;
 }
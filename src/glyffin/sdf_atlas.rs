@@ -0,0 +1,182 @@
+use base::{SignedDistanceField, SDF_SIZE, SDF_SPREAD};
+use glium;
+use glium::backend::Facade;
+use rusttype::{point, Font, GlyphId, Point, Rect, Scale};
+use std::borrow::Cow;
+use rendering::{merge_approaches, Layers};
+use std::collections::HashMap;
+use super::{by_approach, layout_vertices, LaidQuip, Vertex};
+
+const CELLS_PER_SIDE: u32 = 16;
+const REFERENCE_SCALE: f32 = 48.0;
+
+#[derive(Copy, Clone, Debug)]
+struct SdfGlyph {
+    page: usize,
+    uv_rect: Rect<f32>,
+    reference_scale: f32,
+    origin: Point<f32>,
+}
+
+struct SdfPage {
+    texture: glium::texture::Texture2d,
+    vertex_buffer: glium::VertexBuffer<Vertex>,
+    layers: Layers,
+}
+
+impl SdfPage {
+    fn new<F: Facade>(display: &F) -> Self {
+        let side = CELLS_PER_SIDE * SDF_SIZE as u32;
+        let texture = glium::texture::Texture2d::with_format(
+            display,
+            glium::texture::RawImage2d {
+                data: Cow::Owned(vec![0u8; side as usize * side as usize]),
+                width: side,
+                height: side,
+                format: glium::texture::ClientFormat::U8,
+            },
+            glium::texture::UncompressedFloatFormat::U8,
+            glium::texture::MipmapsOption::NoMipmap,
+        ).unwrap();
+        SdfPage { texture, vertex_buffer: glium::VertexBuffer::new(display, &[]).unwrap(), layers: Layers::default() }
+    }
+}
+
+// Keeps one distance field per glyph regardless of the scale it is drawn at.
+pub struct SdfAtlas {
+    pages: Vec<SdfPage>,
+    glyphs: HashMap<u32, Option<SdfGlyph>>,
+    cell_count: u32,
+}
+
+impl SdfAtlas {
+    pub fn new() -> Self {
+        SdfAtlas { pages: Vec::new(), glyphs: HashMap::new(), cell_count: 0 }
+    }
+
+    pub(super) fn set_quips<F: Facade>(&mut self, font: &Font, laid_quips: &[LaidQuip], members: &[usize], display: &F) {
+        let mut page_vertices: Vec<(Vec<Vertex>, Layers)> = Vec::new();
+        for index in by_approach(laid_quips, members) {
+            let LaidQuip { quip, ref glyphs } = laid_quips[index];
+            let (x, y) = quip.anchor;
+            for glyph in glyphs {
+                if let Some(sdf_glyph) = self.glyph(font, glyph.id(), display) {
+                    let scale = glyph.scale();
+                    let (x_factor, y_factor) = (scale.x / sdf_glyph.reference_scale, scale.y / sdf_glyph.reference_scale);
+                    let position = glyph.position();
+                    let min = point(x + position.x + sdf_glyph.origin.x * x_factor, y + position.y + sdf_glyph.origin.y * y_factor);
+                    let gl_rect = Rect {
+                        min,
+                        max: point(min.x + SDF_SIZE as f32 * x_factor, min.y + SDF_SIZE as f32 * y_factor),
+                    };
+                    while page_vertices.len() <= sdf_glyph.page {
+                        page_vertices.push(Default::default());
+                    }
+                    let vertices = layout_vertices(quip.approach, &sdf_glyph.uv_rect, &gl_rect, &quip.colour, quip.clip);
+                    let &mut (ref mut page, ref mut layers) = &mut page_vertices[sdf_glyph.page];
+                    layers.push(quip.approach, vertices.len());
+                    page.extend(vertices);
+                }
+            }
+        }
+        let mut page_vertices = page_vertices.into_iter();
+        for page in self.pages.iter_mut() {
+            let (vertices, layers) = page_vertices.next().unwrap_or_default();
+            page.vertex_buffer = glium::VertexBuffer::new(display, &vertices).expect("VertexBuffer::new");
+            page.layers = layers;
+        }
+    }
+
+    pub fn approaches(&self) -> Vec<f32> {
+        merge_approaches(self.pages.iter().map(|page| page.layers.approaches()).collect())
+    }
+
+    pub fn draw(&self, frame: &mut glium::Frame, approach: f32, program: &glium::Program, modelview: [[f32; 4]; 4], draw_parameters: &glium::DrawParameters) {
+        use glium::Surface;
+        for page in &self.pages {
+            let range = match page.layers.range(approach) {
+                Some(range) => range,
+                None => continue,
+            };
+            let sampler = page.texture.sampled()
+                .magnify_filter(glium::uniforms::MagnifySamplerFilter::Linear)
+                .minify_filter(glium::uniforms::MinifySamplerFilter::Linear);
+            let uniforms = uniform! { tex: sampler, modelview: modelview };
+            frame.draw(page.vertex_buffer.slice(range).unwrap(),
+                       glium::index::NoIndices(glium::index::PrimitiveType::TrianglesList),
+                       program,
+                       &uniforms,
+                       draw_parameters)
+                .expect("frame.draw");
+        }
+    }
+
+    fn glyph<F: Facade>(&mut self, font: &Font, id: GlyphId, display: &F) -> Option<SdfGlyph> {
+        if let Some(sdf_glyph) = self.glyphs.get(&id.0) {
+            return *sdf_glyph;
+        }
+        let sdf_glyph = self.render_glyph(font, id, display);
+        self.glyphs.insert(id.0, sdf_glyph);
+        sdf_glyph
+    }
+
+    fn render_glyph<F: Facade>(&mut self, font: &Font, id: GlyphId, display: &F) -> Option<SdfGlyph> {
+        let padding = SDF_SPREAD as i32;
+        let room = (SDF_SIZE as i32 - 2 * padding) as f32;
+        let glyph = match font.glyph(id) {
+            Some(glyph) => glyph,
+            None => return None,
+        };
+        let trial_box = match glyph.clone().scaled(Scale::uniform(REFERENCE_SCALE)).positioned(point(0.0, 0.0)).pixel_bounding_box() {
+            Some(trial_box) => trial_box,
+            None => return None,
+        };
+        let extent = trial_box.width().max(trial_box.height()) as f32;
+        let reference_scale = if extent > room { REFERENCE_SCALE * room / extent } else { REFERENCE_SCALE };
+        let positioned = glyph.scaled(Scale::uniform(reference_scale)).positioned(point(0.0, 0.0));
+        let bounding_box = match positioned.pixel_bounding_box() {
+            Some(bounding_box) => bounding_box,
+            None => return None,
+        };
+        let mut coverage = vec![0.0f32; SDF_SIZE * SDF_SIZE];
+        positioned.draw(|x, y, value| {
+            let (column, row) = (x as usize + SDF_SPREAD, y as usize + SDF_SPREAD);
+            if column < SDF_SIZE && row < SDF_SIZE {
+                coverage[SignedDistanceField::get_index(column, row)] = value;
+            }
+        });
+        let sdf = SignedDistanceField::from_mask(|column, row| coverage[SignedDistanceField::get_index(column, row)] >= 0.5);
+        let (page, uv_rect) = self.store(&sdf, display);
+        let origin = point((bounding_box.min.x - padding) as f32, (bounding_box.min.y - padding) as f32);
+        Some(SdfGlyph { page, uv_rect, reference_scale, origin })
+    }
+
+    fn store<F: Facade>(&mut self, sdf: &SignedDistanceField, display: &F) -> (usize, Rect<f32>) {
+        let cells_per_page = CELLS_PER_SIDE * CELLS_PER_SIDE;
+        let page = (self.cell_count / cells_per_page) as usize;
+        let cell = self.cell_count % cells_per_page;
+        self.cell_count += 1;
+        if page == self.pages.len() {
+            self.pages.push(SdfPage::new(display));
+        }
+        let cell_side = SDF_SIZE as u32;
+        let (left, top) = ((cell % CELLS_PER_SIDE) * cell_side, (cell / CELLS_PER_SIDE) * cell_side);
+        self.pages[page].texture.main_level().write(glium::Rect {
+            left,
+            bottom: top,
+            width: cell_side,
+            height: cell_side,
+        }, glium::texture::RawImage2d {
+            data: Cow::Borrowed(sdf.distances()),
+            width: cell_side,
+            height: cell_side,
+            format: glium::texture::ClientFormat::U8,
+        });
+        let side = (CELLS_PER_SIDE * cell_side) as f32;
+        let uv_rect = Rect {
+            min: point(left as f32 / side, top as f32 / side),
+            max: point((left + cell_side) as f32 / side, (top + cell_side) as f32 / side),
+        };
+        (page, uv_rect)
+    }
+}
@@ -3,14 +3,16 @@ extern crate arrayvec;
 extern crate cage;
 #[macro_use]
 extern crate glium;
+extern crate png;
 extern crate rusttype;
 extern crate unicode_normalization;
 extern crate xml;
 
 pub use anchor::Anchor;
-pub use base::{Color, WebColor, X11Color};
+pub use base::{Bitmap, Color, ImageSource, WebColor, X11Color};
 pub use block::Block;
 pub use glium::glutin::VirtualKeyCode;
+pub use rendering::Lighting;
 pub use sigil::Sigil;
 use std::sync::mpsc::Sender;
 pub use window::WindowNote;
@@ -29,6 +31,7 @@ pub mod color;
 pub mod app;
 pub mod scribe;
 pub mod traits;
+pub mod store;
 pub mod id;
 mod sigil;
 mod local_screen;
@@ -39,6 +42,7 @@ mod block;
 pub enum DirectorMsg {
     ScreenReady(Sender<ScreenMsg>),
     ScreenResized(u32, u32),
+    ScreenDpiFactor(f32),
     ScreenClosed,
     KeyPressed(VirtualKeyCode),
     TouchMsg(TouchMsg),
@@ -49,6 +53,7 @@ pub enum ScreenMsg {
     AddBlock(u64, Block),
     Close,
     Title(String),
+    Lighting(Lighting),
 }
 
 #[derive(Copy, Clone, PartialEq, Debug)]
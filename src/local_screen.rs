@@ -1,13 +1,15 @@
-use ::{Block, Sigil};
+use ::{Block, Color, Sigil};
 use ::{DirectorMsg, ScreenMsg, TouchMsg};
-use ::rendering::{PatchRenderer, ShadowRenderer};
-use ::rendering::model::Patch;
-use glium::{Display, Frame, Surface};
+use ::flood::Gradient;
+use ::rendering::{merge_approaches, Canvas, CanvasRenderer, Image, ImageRenderer, PatchRenderer, ShadowRenderer, SurfaceRenderer};
+use ::rendering::model::{self, clip_bounds, Patch};
+use glium::{Display, Surface};
 use glium::backend::Facade;
 use glium::glutin::{ContextBuilder, ControlFlow, Event, EventsLoop, KeyboardInput, VirtualKeyCode, WindowBuilder, WindowEvent};
 use glium::glutin::{ElementState, MouseButton};
-use glyffin::QuipRenderer;
+use glyffin::{Quip, QuipRenderer};
 use scribe::Scale;
+use std::cmp::Ordering;
 use std::collections::HashMap;
 use std::sync::mpsc::{channel, Receiver, Sender};
 use std::thread;
@@ -24,6 +26,7 @@ pub fn start(width: u32, height: u32, director: Sender<DirectorMsg>) {
     spawn_awakener(&events_loop, awaken_message_sender, screen_msg_receiver);
 
     let mut local_screen = LocalScreen::new(width, height, &events_loop, director.clone());
+    director.send(DirectorMsg::ScreenDpiFactor(local_screen.dpi_factor)).unwrap();
     events_loop.run_forever(|ev| {
         match ev {
             Event::WindowEvent { event, .. } => process_window_event(event, &director, &mut local_screen),
@@ -67,6 +70,16 @@ fn process_window_event(event: WindowEvent, director: &Sender<DirectorMsg>, loca
             director.send(DirectorMsg::ScreenResized(width, height)).unwrap();
             ControlFlow::Continue
         }
+        // Not every platform reports a new dpi factor, so moving the window checks it too.
+        WindowEvent::HiDPIFactorChanged(dpi_factor) => {
+            local_screen.on_dpi_factor(dpi_factor);
+            ControlFlow::Continue
+        }
+        WindowEvent::Moved(_, _) => {
+            let dpi_factor = local_screen.display.gl_window().hidpi_factor();
+            local_screen.on_dpi_factor(dpi_factor);
+            ControlFlow::Continue
+        }
         WindowEvent::Refresh => {
             local_screen.draw();
             ControlFlow::Continue
@@ -88,10 +101,16 @@ fn process_window_event(event: WindowEvent, director: &Sender<DirectorMsg>, loca
 
 pub struct LocalScreen<'a> {
     dimensions: (f32, f32),
+    dpi_factor: f32,
     director: Sender<DirectorMsg>,
     blocks: HashMap<u64, Block>,
+    patches_stale: bool,
+    quips_stale: bool,
     patch_renderer: PatchRenderer,
     shadow_renderer: ShadowRenderer,
+    surface_renderer: SurfaceRenderer,
+    image_renderer: ImageRenderer,
+    canvas_renderer: CanvasRenderer,
     quip_renderer: QuipRenderer<'a>,
     display: Display,
     status: ScreenStatus,
@@ -106,10 +125,16 @@ impl<'a> LocalScreen<'a> {
         let dpi_factor = display.gl_window().hidpi_factor();
         let local_screen = LocalScreen {
             dimensions: (width as f32, height as f32),
+            dpi_factor,
             director,
             blocks: HashMap::<u64, Block>::new(),
+            patches_stale: true,
+            quips_stale: true,
             patch_renderer: PatchRenderer::new(&display, modelview),
             shadow_renderer: ShadowRenderer::new(&display, modelview),
+            surface_renderer: SurfaceRenderer::new(&display, modelview),
+            image_renderer: ImageRenderer::new(&display, modelview),
+            canvas_renderer: CanvasRenderer::new(&display, modelview),
             quip_renderer: QuipRenderer::new(dpi_factor, modelview, &display),
             display,
             status: ScreenStatus::Changed,
@@ -130,12 +155,15 @@ impl<'a> LocalScreen<'a> {
     fn begin_tracking(&mut self) {
         self.cancel_tracking();
         let (x, y) = self.cursor;
-        let some_block = self.blocks.iter().find(|&(_, block)| {
-            match block.sigil {
-                Sigil::Touch(_) if block.is_hit(x as f32, y as f32) => true,
+        // The nearest touch block wins. On the same plane, the later block wins.
+        let some_block = self.blocks.iter()
+            .filter(|&(_, block)| match block.sigil {
+                Sigil::Touch(_) => block.is_hit(x as f32, y as f32),
                 _ => false,
-            }
-        });
+            })
+            .max_by(|&(a_id, a), &(b_id, b)| {
+                a.approach.partial_cmp(&b.approach).unwrap_or(Ordering::Equal).then(a_id.cmp(b_id))
+            });
         if let Some((_, &Block { sigil: Sigil::Touch(tag), .. })) = some_block {
             self.touch_destination = Some(tag);
             self.send_touch(TouchMsg::Begin(tag, x, y));
@@ -170,16 +198,32 @@ impl<'a> LocalScreen<'a> {
         self.dimensions = (width as f32, height as f32);
         self.patch_renderer.set_modelview(modelview);
         self.shadow_renderer.set_modelview(modelview);
+        self.surface_renderer.set_modelview(modelview);
+        self.image_renderer.set_modelview(modelview);
+        self.canvas_renderer.set_modelview(modelview);
         self.quip_renderer.set_modelview(modelview);
+        self.patches_stale = true;
         self.draw();
     }
 
+    fn on_dpi_factor(&mut self, dpi_factor: f32) {
+        if dpi_factor != self.dpi_factor {
+            self.dpi_factor = dpi_factor;
+            self.quips_stale = true;
+            self.director.send(DirectorMsg::ScreenDpiFactor(dpi_factor)).unwrap();
+        }
+    }
+
     pub fn update(&mut self, screen_message: ScreenMsg) {
         match screen_message {
             ScreenMsg::AddBlock(id, block) => {
-                let blocks = &mut self.blocks;
-                blocks.insert(id, block);
-                self.status = self.status.did_change()
+                if self.blocks.get(&id) != Some(&block) {
+                    let blocks = &mut self.blocks;
+                    blocks.insert(id, block);
+                    self.patches_stale = true;
+                    self.quips_stale = true;
+                    self.status = self.status.did_change()
+                }
             }
             ScreenMsg::Close => {
                 self.status = self.status.will_close()
@@ -187,57 +231,145 @@ impl<'a> LocalScreen<'a> {
             ScreenMsg::Title(title) => {
                 self.display.gl_window().set_title(&title);
             }
+            ScreenMsg::Lighting(lighting) => {
+                self.shadow_renderer.set_lighting(lighting);
+                self.patches_stale = true;
+                self.status = self.status.did_change()
+            }
         }
     }
 
     fn draw(&mut self) {
         let mut target = self.display.draw();
         target.clear_color_and_depth((0.70, 0.80, 0.90, 1.0), 1.0);
-        self.draw_patches(&mut target);
-        self.draw_quips(&mut target);
+        self.set_patches();
+        self.set_quips();
+        self.patch_renderer.draw_opaque(&mut target);
+        // Everything that blends, shadows and text included, goes back-to-front one approach
+        // at a time without writing depth, so nothing hides what is blended behind it later.
+        let approaches = merge_approaches(vec![
+            self.shadow_renderer.approaches(),
+            self.patch_renderer.translucent_approaches(),
+            self.surface_renderer.approaches(),
+            self.image_renderer.approaches(),
+            self.canvas_renderer.approaches(),
+            self.quip_renderer.approaches(),
+        ]);
+        for approach in approaches {
+            self.shadow_renderer.draw(&mut target, approach);
+            self.patch_renderer.draw_translucent(&mut target, approach);
+            self.surface_renderer.draw(&mut target, approach);
+            self.image_renderer.draw(&mut target, approach);
+            self.canvas_renderer.draw(&mut target, approach);
+            self.quip_renderer.draw(&mut target, approach);
+        }
         target.finish().unwrap();
         self.status = self.status.did_draw();
     }
 
-    fn draw_quips(&mut self, target: &mut Frame) {
-        let quip_renderer = &mut self.quip_renderer;
-        let blocks = &self.blocks;
-        let dpi_factor = self.display.gl_window().hidpi_factor();
-        let display = &self.display;
-        blocks.iter().for_each(|(_, block)| {
-            if let Sigil::Paragraph { line_height, ref text, ref color, placement } = block.sigil {
-                quip_renderer.layout_paragraph(
-                    text,
-                    block.anchor.into(),
-                    Scale::uniform(line_height * dpi_factor),
-                    block.width as i32,
-                    block.approach,
-                    color.to_gl(),
-                    placement,
-                    display,
-                );
-                quip_renderer.draw(target);
-            }
-        });
+    fn set_quips(&mut self) {
+        if self.quips_stale {
+            let dpi_factor = self.dpi_factor;
+            let quips = ordered_blocks(&self.blocks).into_iter()
+                .filter_map(|block| match block.sigil {
+                    Sigil::Paragraph { line_height, ref text, ref color, placement } => Some(Quip {
+                        text,
+                        anchor: block.anchor.into(),
+                        scale: Scale::uniform(line_height * dpi_factor),
+                        width: (block.width * dpi_factor) as i32,
+                        height: (block.height * dpi_factor).ceil() as i32,
+                        approach: block.approach,
+                        colour: color.to_gl(),
+                        placement,
+                        clip: clip_bounds(&block.clip),
+                    }),
+                    _ => None,
+                })
+                .collect::<Vec<_>>();
+            self.quip_renderer.set_quips(&quips, &self.display);
+            self.quips_stale = false;
+        }
     }
 
-    fn draw_patches(&mut self, target: &mut Frame) {
-        let patch_renderer = &mut self.patch_renderer;
-        let shadow_renderer = &mut self.shadow_renderer;
-        let dimensions = self.dimensions;
-        let blocks = &self.blocks;
-        blocks.iter().for_each(|(_, block)| {
-            if let Sigil::Color(color) = block.sigil {
-                let patch = Patch::new(block.anchor.into(), block.width, block.height, block.approach, color);
-                patch_renderer.set_patch(&patch);
-                patch_renderer.draw(target);
-                shadow_renderer.set_patch(&patch, dimensions);
-                shadow_renderer.draw(target);
-            }
-        });
+    fn set_patches(&mut self) {
+        if self.patches_stale {
+            let blocks = ordered_blocks(&self.blocks);
+            let patches = blocks.iter()
+                .filter_map(|block| match block.sigil {
+                    Sigil::Color(color) => Some(block_patch(block, color)),
+                    Sigil::Gradient(ref gradient) => Some(gradient_patch(block, gradient)),
+                    _ => None,
+                })
+                .collect::<Vec<_>>();
+            let surfaces = blocks.iter()
+                .filter_map(|block| match block.sigil {
+                    Sigil::Surface { color, corners, border_width, border_color } => {
+                        Some(model::Surface { patch: block_patch(block, color), corners, border_width, border_color })
+                    }
+                    _ => None,
+                })
+                .collect::<Vec<_>>();
+            let images = blocks.iter()
+                .filter_map(|block| match block.sigil {
+                    Sigil::Image { ref source, fit, tint } => Some(Image {
+                        source: source.clone(),
+                        fit,
+                        tint,
+                        anchor: block.anchor.into(),
+                        width: block.width,
+                        height: block.height,
+                        approach: block.approach,
+                        clip: block.clip,
+                    }),
+                    _ => None,
+                })
+                .collect::<Vec<_>>();
+            let canvases = blocks.iter()
+                .filter_map(|block| match block.sigil {
+                    Sigil::Canvas { ref paths, ref inks } => Some(Canvas {
+                        anchor: block.anchor.into(),
+                        approach: block.approach,
+                        paths,
+                        inks,
+                        clip: block.clip,
+                    }),
+                    _ => None,
+                })
+                .collect::<Vec<_>>();
+            let shadow_casters = blocks.iter()
+                .filter(|block| block.casts_shadow)
+                .filter_map(|block| match block.sigil {
+                    Sigil::Color(color) => Some((block_patch(block, color), [0.0; 4])),
+                    Sigil::Surface { color, corners, .. } => Some((block_patch(block, color), corners)),
+                    Sigil::Gradient(ref gradient) => Some((gradient_patch(block, gradient), [0.0; 4])),
+                    _ => None,
+                })
+                .collect::<Vec<_>>();
+            self.patch_renderer.set_patches(&patches, &self.display);
+            self.surface_renderer.set_surfaces(&surfaces, &self.display);
+            self.image_renderer.set_images(&images, &self.display);
+            self.canvas_renderer.set_canvases(&canvases, &self.display);
+            self.shadow_renderer.set_casters(&shadow_casters, self.dimensions, &self.display);
+            self.patches_stale = false;
+        }
     }
 }
 
+fn block_patch(block: &Block, color: Color) -> Patch {
+    Patch::new(block.anchor.into(), block.width, block.height, block.approach, color).with_clip(block.clip)
+}
+
+fn gradient_patch(block: &Block, gradient: &Gradient) -> Patch {
+    Patch::with_gradient(block.anchor.into(), block.width, block.height, block.approach, gradient.clone()).with_clip(block.clip)
+}
+
+// Block ids fix the order of blocks that share an approach so blending is repeatable.
+fn ordered_blocks(blocks: &HashMap<u64, Block>) -> Vec<&Block> {
+    let mut ids = blocks.keys().cloned().collect::<Vec<_>>();
+    ids.sort();
+    ids.iter().map(|id| &blocks[id]).collect()
+}
+
 pub enum AwakenMessage {
     ScreenMessage(ScreenMsg)
 }
@@ -1,11 +1,12 @@
 use Color;
+use color::argb::TRANSPARENT;
 use flood::*;
 use flood::Signal;
 use flood::VersionCounter;
 use TouchMsg;
 use std::sync::Arc;
 use material::palette::Palette;
-use traits::Update;
+use traits::{Component, Update};
 pub use flood::Placement;
 
 #[derive(Clone)]
@@ -52,6 +53,35 @@ impl<'a, MsgT, F> From<Button<'a, MsgT, F>> for Flood<MsgT> where
     }
 }
 
+// The flat button as a component. Its view raises ButtonEvt::Click once per completed press.
+#[derive(Clone, PartialEq, Debug)]
+pub struct FlatButton {
+    pub kind: ButtonKind,
+    pub placement: Placement,
+    pub palette: Palette,
+}
+
+impl Component for FlatButton {
+    type Mdl = ButtonMdl;
+    type Msg = ButtonMsg;
+    type Evt = ButtonEvt;
+
+    fn view<MsgT, F, G>(&self, id: u64, mdl: &ButtonMdl, msg_wrap: F, evt_wrap: G) -> Flood<MsgT> where
+        MsgT: Clone + Send + Sync + 'static,
+        F: Fn(ButtonMsg) -> MsgT + Send + Sync + 'static,
+        G: Fn(ButtonEvt) -> MsgT + Send + Sync + 'static,
+    {
+        Flood::from(Button {
+            msg_wrap,
+            id,
+            palette: &self.palette,
+            mdl,
+            style: vec![ButtonStyle::Kind(self.kind.clone()), ButtonStyle::Placement(self.placement)],
+            click_msg: evt_wrap(ButtonEvt::Click),
+        })
+    }
+}
+
 #[derive(Clone, PartialEq, Debug)]
 pub enum ButtonStyle {
     Placement(Placement),
@@ -165,12 +195,19 @@ fn draw<MsgT, F>(button: &Button<MsgT, F>) -> Flood<MsgT> where
     }
 }
 
+// The label takes its preferred size, a line of text with padding around it, and is
+// placed along the button. The button itself may be any size.
 fn flat_button_surface<MsgT>(label: &str, text_color: Color, placement: Placement) -> Flood<MsgT> where
     MsgT: Clone
 {
-    let text = Flood::Text(label.to_uppercase(), text_color, placement);
-    let padding = Padding::Dual(Length::Spacing, Length::Full / 4);
-    text + padding
+    let text = Flood::Text(label.to_uppercase(), text_color, Placement::Center);
+    let padded = text + Padding::Dual(Length::Spacing, Length::Spacing / 2);
+    let placement: f32 = placement.into();
+    Flood::Stream(Stack::Row(Length::Zero, Cross::Place(Placement::Center)), vec![
+        (Span::Weight(placement), Flood::Color(TRANSPARENT)),
+        (Span::Intrinsic, padded),
+        (Span::Weight(1.0 - placement), Flood::Color(TRANSPARENT)),
+    ])
 }
 
 fn text_color(kind: &ButtonKind, palette: &Palette) -> Color {
@@ -201,6 +238,11 @@ pub enum ButtonMsg {
     None,
 }
 
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum ButtonEvt {
+    Click,
+}
+
 #[derive(Copy, Clone, Eq, PartialEq, Debug)]
 pub enum PressState {
     Up,
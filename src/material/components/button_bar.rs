@@ -1,8 +1,8 @@
-pub struct Button<MsgT> {
+#[derive(Clone, PartialEq, Debug)]
+pub struct Button {
     pub id: u64,
     pub label: String,
     pub intent: ButtonIntent,
-    pub click_msg: MsgT,
 }
 
 #[derive(Copy, Clone, Eq, PartialEq, Debug)]
@@ -12,33 +12,43 @@ pub enum ButtonIntent {
     Inform,
 }
 
-pub struct ButtonBar<'a, MsgT, F> where
-    F: Fn(ButtonBarMsg) -> MsgT + Send + Sync + 'static,
-{
-    pub msg_wrap: F,
-    pub palette: &'a Palette,
-    pub button_bar_mdl: &'a button_bar::ButtonBarMdl,
-    pub buttons: Vec<Button<MsgT>>,
+// A row of text buttons. Its view raises ButtonBarEvt::Click with the id of each button
+// clicked.
+#[derive(Clone, PartialEq, Debug)]
+pub struct ButtonBar {
+    pub palette: Palette,
+    pub buttons: Vec<Button>,
+}
+
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum ButtonBarEvt {
+    Click(u64),
 }
 
 pub use self::button_bar::*;
 use ::material::Palette;
 use ::flood::*;
+use ::traits::Component;
 use std::sync::Arc;
 
-impl<'a, MsgT, F> Into<Flood<MsgT>> for ButtonBar<'a, MsgT, F> where
-    F: Fn(ButtonBarMsg) -> MsgT + Send + Sync + 'static,
-    MsgT: Clone + Send + Sync + 'static,
-{
-    fn into(self) -> Flood<MsgT> {
-        let msg_wrap = Arc::new(self.msg_wrap);
-        let palette = self.palette;
-        let button_mdls = &self.button_bar_mdl.button_mdls;
+impl Component for ButtonBar {
+    type Mdl = ButtonBarMdl;
+    type Msg = ButtonBarMsg;
+    type Evt = ButtonBarEvt;
+
+    fn view<MsgT, F, G>(&self, _id: u64, mdl: &ButtonBarMdl, msg_wrap: F, evt_wrap: G) -> Flood<MsgT> where
+        MsgT: Clone + Send + Sync + 'static,
+        F: Fn(ButtonBarMsg) -> MsgT + Send + Sync + 'static,
+        G: Fn(ButtonBarEvt) -> MsgT + Send + Sync + 'static,
+    {
+        let msg_wrap = Arc::new(msg_wrap);
+        let palette = &self.palette;
+        let button_mdls = &mdl.button_mdls;
         let default_button_mdl = ButtonMdl::default();
         use self::button::ButtonMdl;
 
-        self.buttons.into_iter().rev()
-            .fold(Flood::Color(self.palette.transparent), |bar, button| {
+        let children = self.buttons.iter()
+            .flat_map(|button| {
                 let button_mdl = match button_mdls.get(&button.id) {
                     Some(button_mdl) => button_mdl,
                     None => &default_button_mdl,
@@ -77,15 +87,17 @@ impl<'a, MsgT, F> Into<Flood<MsgT>> for ButtonBar<'a, MsgT, F> where
 
                     let signal_sensor = {
                         let button_id = button.id;
-                        let versioned_click_msg: Version<MsgT> = (button.click_msg, button_mdl.click_msg_version_counter).into();
+                        let versioned_click_msg: Version<MsgT> = (evt_wrap(ButtonBarEvt::Click(button.id)), button_mdl.click_msg_version_counter).into();
                         Sensor::Signal(Signal::from((button_id, versioned_click_msg)))
                     };
                     surface + feedback + touch_sensor + signal_sensor
                 };
                 let full_button_length = Length::Text(string.clone()) / 2 + (surface_padding_length.clone() * 2);
                 let spacer = Flood::Color(palette.transparent);
-                bar + (Position::Left(full_button_length), full_button) + (Position::Left(Length::Spacing / 2), spacer)
+                vec![(Span::Fixed(Length::Spacing / 2), spacer), (Span::Fixed(full_button_length), full_button)]
             })
+            .collect();
+        Flood::Stream(Stack::Row(Length::Zero, Cross::Fill), children)
     }
 }
 
@@ -104,24 +116,27 @@ pub mod button_bar {
         }
     }
 
-    pub fn update_button_bar(mdl: &mut ButtonBarMdl, msg: ButtonBarMsg) {
-        match msg {
-            ButtonBarMsg::Touch(button_id, touch_msg) => {
-                let button_msg = ButtonMsg::Touch(touch_msg);
-                let button_mdls = &mut mdl.button_mdls;
-                if button_mdls.contains_key(&button_id) {
-                    let button_mdl = button_mdls.get_mut(&button_id).unwrap();
-                    update_button_mdl(button_mdl, button_msg);
-                } else {
-                    let mut new_button_mdl = ButtonMdl::default();
-                    update_button_mdl(&mut new_button_mdl, button_msg);
-                    button_mdls.insert(button_id, new_button_mdl);
+    impl Update<ButtonBarMsg> for ButtonBarMdl {
+        fn update(&mut self, msg: ButtonBarMsg) {
+            match msg {
+                ButtonBarMsg::Touch(button_id, touch_msg) => {
+                    let button_msg = ButtonMsg::Touch(touch_msg);
+                    let button_mdls = &mut self.button_mdls;
+                    if button_mdls.contains_key(&button_id) {
+                        let button_mdl = button_mdls.get_mut(&button_id).unwrap();
+                        update_button_mdl(button_mdl, button_msg);
+                    } else {
+                        let mut new_button_mdl = ButtonMdl::default();
+                        update_button_mdl(&mut new_button_mdl, button_msg);
+                        button_mdls.insert(button_id, new_button_mdl);
+                    }
                 }
             }
         }
     }
 
     use super::button::{update_button_mdl, ButtonMsg};
+    use traits::Update;
 
     #[derive(Clone, PartialEq, Debug)]
     pub enum ButtonBarMsg {
@@ -7,22 +7,9 @@ use material::components::button::*;
 use material::components::button::Placement;
 use material::components::button::ButtonStyle;
 use id::*;
-use traits::Update;
+use traits::{Component, Update};
 use flood::{Version, VersionCounter, Sensor, Signal};
 
-#[derive(Clone, PartialEq, Debug)]
-pub struct NavTrack<'a, MsgT, F, G> where
-    F: Fn(NavTrackMsg) -> MsgT + Send + Sync + 'static,
-    G: Fn(NavTrackEvt) -> MsgT + Send + Sync + 'static,
-{
-    pub nav_track_msg_wrap: F,
-    pub id: u64,
-    pub palette: &'a Palette,
-    pub mdl: &'a NavTrackMdl,
-    pub nav_track_evt_wrap: G,
-    pub items: Vec<NavTrackItem>,
-}
-
 #[derive(Clone, PartialEq, Debug)]
 pub enum NavTrackEvt {
     ItemSelected(Option<usize>)
@@ -67,7 +54,7 @@ impl Update<NavTrackMsg> for NavTrackMdl {
     }
 }
 
-#[derive(Clone, PartialEq, Debug)]
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
 pub enum NavTrackMsg {
     SelectItem(usize),
     ButtonMsgWrap(u64, ButtonMsg),
@@ -78,17 +65,29 @@ pub struct NavTrackItem {
     pub label: String,
 }
 
-impl<'a, MsgT, F, G> From<NavTrack<'a, MsgT, F, G>> for Flood<MsgT> where
-    MsgT: Clone,
-    F: Fn(NavTrackMsg) -> MsgT + Send + Sync + 'static,
-    G: Fn(NavTrackEvt) -> MsgT + Send + Sync + 'static,
-{
-    fn from(nav_track: NavTrack<'a, MsgT, F, G>) -> Self {
-        let palette = nav_track.palette;
-        let track_msg_wrap = Arc::new(nav_track.nav_track_msg_wrap);
-        let items = nav_track.items;
-        let button_ids = nav_track.id.sub_ids(Angle::A, items.len());
-        let nav_track_mdl = nav_track.mdl;
+// A track of navigation items. Its view raises NavTrackEvt::ItemSelected whenever the
+// selection changes.
+#[derive(Clone, PartialEq, Debug)]
+pub struct NavTrack {
+    pub palette: Palette,
+    pub items: Vec<NavTrackItem>,
+}
+
+impl Component for NavTrack {
+    type Mdl = NavTrackMdl;
+    type Msg = NavTrackMsg;
+    type Evt = NavTrackEvt;
+
+    fn view<MsgT, F, G>(&self, id: u64, mdl: &NavTrackMdl, msg_wrap: F, evt_wrap: G) -> Flood<MsgT> where
+        MsgT: Clone + Send + Sync + 'static,
+        F: Fn(NavTrackMsg) -> MsgT + Send + Sync + 'static,
+        G: Fn(NavTrackEvt) -> MsgT + Send + Sync + 'static,
+    {
+        let palette = &self.palette;
+        let track_msg_wrap = Arc::new(msg_wrap);
+        let items = &self.items;
+        let button_ids = id.sub_ids(Angle::A, items.len());
+        let nav_track_mdl = mdl;
         let default_button_mdl = &ButtonMdl::default();
         let buttons = items.iter().enumerate()
             .map(|(i, item)| {
@@ -123,12 +122,12 @@ impl<'a, MsgT, F, G> From<NavTrack<'a, MsgT, F, G>> for Flood<MsgT> where
                 panel + (Position::Top(material::Length::ListItemHeight.into()), item)
             });
 
-        let item_selected_msg = (nav_track.nav_track_evt_wrap)(NavTrackEvt::ItemSelected(nav_track_mdl.selected_index.clone()));
+        let item_selected_msg = evt_wrap(NavTrackEvt::ItemSelected(nav_track_mdl.selected_index.clone()));
         let versioned_item_selected_msg = Version { value: item_selected_msg, counter: nav_track_mdl.item_selected_version_counter.clone() };
         buttons
             + Padding::Vertical(material::Length::ListGroupPadding.into())
             + Flood::Color(palette.dark_background_raised)
             + Padding::Behind(material::Length::NavApproach.into())
-            + Sensor::Signal(Signal { id: nav_track.id, version: versioned_item_selected_msg })
+            + Sensor::Signal(Signal { id, version: versioned_item_selected_msg })
     }
 }
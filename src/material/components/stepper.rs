@@ -1,18 +1,19 @@
 use ::flood::*;
 use ::material;
+use ::traits::{Component, Update};
 
-#[derive(Clone, Debug)]
-pub struct Stepper<'a, MsgT> where MsgT: Clone {
-    pub palette: &'a material::Palette,
-    pub id: Vec<u64>,
+// The bar of steps across the top of a stepper. Parents place the active step's content
+// below it.
+#[derive(Clone, PartialEq, Debug)]
+pub struct Stepper {
+    pub palette: material::Palette,
     pub active_index: usize,
-    pub active_content: Flood<MsgT>,
-    pub steps: Vec<Step<'a>>,
+    pub steps: Vec<Step>,
 }
 
-#[derive(Copy, Clone, PartialEq, Debug)]
-pub struct Step<'a> {
-    pub label: &'a str,
+#[derive(Clone, PartialEq, Debug)]
+pub struct Step {
+    pub label: String,
 }
 
 #[derive(Copy, Clone, Eq, PartialEq, Debug)]
@@ -25,24 +26,30 @@ pub enum StepCondition {
 #[derive(Copy, Clone, Eq, PartialEq, Debug)]
 pub enum StepperMsg {}
 
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum StepperEvt {}
+
 #[derive(Copy, Clone, Eq, PartialEq, Debug, Default)]
 pub struct StepperMdl {}
 
-impl<'a, MsgT> Into<Flood<MsgT>> for Stepper<'a, MsgT> where MsgT: Clone {
-    fn into(self) -> Flood<MsgT> {
+impl Update<StepperMsg> for StepperMdl {
+    fn update(&mut self, msg: StepperMsg) {
+        match msg {}
+    }
+}
+
+impl Component for Stepper {
+    type Mdl = StepperMdl;
+    type Msg = StepperMsg;
+    type Evt = StepperEvt;
+
+    fn view<MsgT, F, G>(&self, _id: u64, _mdl: &StepperMdl, _msg_wrap: F, _evt_wrap: G) -> Flood<MsgT> where
+        MsgT: Clone + Send + Sync + 'static,
+        F: Fn(StepperMsg) -> MsgT + Send + Sync + 'static,
+        G: Fn(StepperEvt) -> MsgT + Send + Sync + 'static,
+    {
         use self::bar::Bar;
-        let palette = self.palette;
-        let active_index = self.active_index;
-        let steps = self.steps;
-        let bar: Flood<MsgT> = Bar { palette, active_index, steps }.into();
-
-        let raised_details = self.active_content
-            + (Stratum::JustBelow, Flood::Color(palette.light_background_raised))
-            + Padding::Behind(Length::CardApproach);
-
-        raised_details
-            + (Position::Top(Length::Spacing / 2), Flood::Color(palette.transparent))
-            + (Position::Top(Length::Full * 0.10), bar)
+        Bar { palette: &self.palette, active_index: self.active_index, steps: &self.steps }.into()
     }
 }
 
@@ -58,7 +65,7 @@ mod bar {
     pub struct Bar<'a> {
         pub palette: &'a Palette,
         pub active_index: usize,
-        pub steps: Vec<Step<'a>>,
+        pub steps: &'a [Step],
     }
 
     impl<'a, MsgT> Into<Flood<MsgT>> for Bar<'a> where MsgT: Clone {
@@ -70,9 +77,8 @@ mod bar {
             } else {
                 let active_index = self.active_index;
                 let last_index = self.steps.len() - 1;
-                let enumerated_steps = self.steps.into_iter().enumerate().collect::<Vec<_>>();
-                enumerated_steps.into_iter().fold(Flood::Color(palette.transparent), |flood, (index, step)| {
-                    let text = step.label;
+                let children = self.steps.iter().enumerate().flat_map(|(index, step)| {
+                    let text = step.label.as_str();
                     let condition = if index < active_index {
                         StepCondition::Completed
                     } else if index == active_index {
@@ -83,21 +89,22 @@ mod bar {
                     let badge: Flood<MsgT> = Badge { palette, digit: index as u32 + 1, condition }.into();
                     let gap = Flood::Color(palette.transparent);
                     let label: Flood<MsgT> = Label { palette, text, condition }.into();
-                    let flood = if index < last_index {
+                    if index < last_index {
                         let spacer: Flood<MsgT> = Spacer { palette }.into();
                         let segment = spacer
                             + (Position::Left(Length::Text(text.to_owned())), label)
                             + (Position::Left(Length::Cross / 3), gap)
                             + (Position::Left(Length::Cross), badge);
-                        flood + (Position::Right(Length::Full / (index + 1)), segment)
+                        vec![(Span::Weight(1.0), segment)]
                     } else {
-                        flood
-                            + (Position::Right(Length::Cross), badge)
-                            + (Position::Right(Length::Cross / 3), gap)
-                            + (Position::Right(Length::Text(text.to_owned())), label)
-                    };
-                    flood
-                })
+                        vec![
+                            (Span::Fixed(Length::Cross), badge),
+                            (Span::Fixed(Length::Cross / 3), gap),
+                            (Span::Fixed(Length::Text(text.to_owned())), label),
+                        ]
+                    }
+                }).collect();
+                Flood::Stream(Stack::Row(Length::Zero, Cross::Fill), children)
             };
             flood + Padding::Uniform(Length::Cross * 0.3)
                 + (Stratum::JustBelow, Flood::Color(palette.light_background_raised))
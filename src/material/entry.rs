@@ -2,21 +2,16 @@ use ::Color;
 use ::color::argb;
 use ::flood::*;
 use ::material;
+use ::traits::{Component, Update};
 
+// A text entry that takes input while it is drawn.
 #[derive(Clone, Eq, PartialEq, Debug)]
-pub struct Entry<F, MsgT> where
-    F: Fn(Msg) -> MsgT + Send + Sync + 'static,
-{
-    pub msg_wrap: F,
-    pub id: u64,
-    pub mdl: Mdl,
+pub struct Entry {
     pub label: String,
     pub placeholder: Option<String>,
 }
 
-impl<F, MsgT> Entry<F, MsgT> where
-    F: Fn(Msg) -> MsgT, F: Send + Sync + 'static,
-{
+impl Entry {
     fn placeholder_string(&self) -> String {
         if let Some(ref placeholder) = self.placeholder {
             placeholder.to_owned()
@@ -55,36 +50,41 @@ pub enum Msg {
     Input(Input),
 }
 
-pub fn update(mdl: &mut Mdl, msg: Msg) {
-    match msg {
-        Msg::ToggleBlink => {
-            mdl.cursor_visibility = match mdl.cursor_visibility {
-                CursorVisibility::Visible => CursorVisibility::Invisible,
-                CursorVisibility::Invisible => CursorVisibility::Visible,
-            };
-            mdl.blink_timeout_version_counter.bump();
-        }
-        Msg::Input(input) => {
-            match input {
-                Input::Insert(string) => {
-                    mdl.pretext = match &mdl.pretext {
-                        &Some(ref pretext) => {
-                            let combined = pretext.to_owned() + &string;
-                            trim_pretext(&combined)
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum Evt {}
+
+impl Update<Msg> for Mdl {
+    fn update(&mut self, msg: Msg) {
+        match msg {
+            Msg::ToggleBlink => {
+                self.cursor_visibility = match self.cursor_visibility {
+                    CursorVisibility::Visible => CursorVisibility::Invisible,
+                    CursorVisibility::Invisible => CursorVisibility::Visible,
+                };
+                self.blink_timeout_version_counter.bump();
+            }
+            Msg::Input(input) => {
+                match input {
+                    Input::Insert(string) => {
+                        self.pretext = match &self.pretext {
+                            &Some(ref pretext) => {
+                                let combined = pretext.to_owned() + &string;
+                                trim_pretext(&combined)
+                            }
+                            &None => trim_pretext(&string),
                         }
-                        &None => trim_pretext(&string),
                     }
-                }
-                Input::DeleteBack => {
-                    mdl.pretext = match &mdl.pretext {
-                        &Some(ref pretext) => {
-                            if pretext.len() > 1 {
-                                trim_pretext(&pretext[..pretext.len() - 1])
-                            } else {
-                                None
+                    Input::DeleteBack => {
+                        self.pretext = match &self.pretext {
+                            &Some(ref pretext) => {
+                                if pretext.len() > 1 {
+                                    trim_pretext(&pretext[..pretext.len() - 1])
+                                } else {
+                                    None
+                                }
                             }
+                            &None => None
                         }
-                        &None => None
                     }
                 }
             }
@@ -101,30 +101,36 @@ fn trim_pretext(pretext: &str) -> Option<String> {
     }
 }
 
-pub fn flood<F, MsgT>(entry: Entry<F, MsgT>) -> Flood<MsgT> where
-    MsgT: Clone + 'static,
-    F: Fn(Msg) -> MsgT, F: Send + Sync + 'static,
-{
-    use std::sync::Arc;
+impl Component for Entry {
+    type Mdl = Mdl;
+    type Msg = Msg;
+    type Evt = Evt;
 
-    let surface = draw_focused_entry(&entry);
-    let blink_timeout = Timeout {
-        id: entry.id,
-        msg: (entry.msg_wrap)(Msg::ToggleBlink),
-        duration: Duration::Milliseconds(500),
-    };
-    let versioned_blink = Version::restore(blink_timeout, entry.mdl.blink_timeout_version_counter);
-    let input_wrap = {
-        Arc::new(move |input| (entry.msg_wrap)(Msg::Input(input)))
-    };
-    surface
-        + Sensor::Timeout(versioned_blink)
-        + Sensor::Input(input_wrap)
+    fn view<MsgT, F, G>(&self, id: u64, mdl: &Mdl, msg_wrap: F, _evt_wrap: G) -> Flood<MsgT> where
+        MsgT: Clone + Send + Sync + 'static,
+        F: Fn(Msg) -> MsgT + Send + Sync + 'static,
+        G: Fn(Evt) -> MsgT + Send + Sync + 'static,
+    {
+        use std::sync::Arc;
+
+        let surface = draw_focused_entry(self, mdl);
+        let blink_timeout = Timeout {
+            id,
+            msg: msg_wrap(Msg::ToggleBlink),
+            duration: Duration::Milliseconds(500),
+        };
+        let versioned_blink = Version::restore(blink_timeout, mdl.blink_timeout_version_counter);
+        let input_wrap = {
+            Arc::new(move |input| msg_wrap(Msg::Input(input)))
+        };
+        surface
+            + Sensor::Timeout(versioned_blink)
+            + Sensor::Input(input_wrap)
+    }
 }
 
-fn draw_focused_entry<F, MsgT>(entry: &Entry<F, MsgT>) -> Flood<MsgT> where
+fn draw_focused_entry<MsgT>(entry: &Entry, mdl: &Mdl) -> Flood<MsgT> where
     MsgT: Clone,
-    F: Fn(Msg) -> MsgT, F: Send + Sync + 'static,
 {
     const LABEL_TOP_PADDING: f32 = 16.0;
     const LABEL_HEIGHT: f32 = 12.0;
@@ -139,7 +145,7 @@ fn draw_focused_entry<F, MsgT>(entry: &Entry<F, MsgT>) -> Flood<MsgT> where
     let accent_dark_color: Color = material::Color::PinkA700.into();
 
     let placeholder = {
-        if entry.mdl.pretext.is_none() {
+        if mdl.pretext.is_none() {
             let placeholder_color: Color = material::Color::LightBackgroundTextDisabled.into();
             Flood::Text(entry.placeholder_string(), placeholder_color, Placement::Start)
         } else {
@@ -148,7 +154,7 @@ fn draw_focused_entry<F, MsgT>(entry: &Entry<F, MsgT>) -> Flood<MsgT> where
     };
     let input = {
         let cursor = {
-            let cursor_color: Color = match entry.mdl.cursor_visibility {
+            let cursor_color: Color = match mdl.cursor_visibility {
                 CursorVisibility::Visible => accent_dark_color,
                 CursorVisibility::Invisible => argb::TRANSPARENT,
             };
@@ -157,7 +163,7 @@ fn draw_focused_entry<F, MsgT>(entry: &Entry<F, MsgT>) -> Flood<MsgT> where
         let runway = Flood::Color(argb::TRANSPARENT);
         let cursor_width = Length::Pixels(1.0);
         let cursor_and_runway = runway + (Position::Left(cursor_width.clone()), cursor);
-        match entry.mdl.pretext {
+        match mdl.pretext {
             Some(ref pretext_string) => {
                 let color: Color = material::color::Color::LightBackgroundTextPrimary.into();
                 let flood = Flood::Text(pretext_string.to_owned(), color, Placement::Start);
@@ -15,9 +15,6 @@ pub enum Length {
     ListItemHeight,
     ListItemPadding,
     ListGroupPadding,
-    ButtonHeight,
-    ButtonTopBottomPadding,
-    ButtonWidth(String),
 }
 
 use flood;
@@ -40,9 +37,6 @@ impl Into<flood::Length> for Length {
             Length::ListItemHeight => flood::Length::Pixels(48.0),
             Length::ListItemPadding => flood::Length::Spacing,
             Length::ListGroupPadding => flood::Length::Pixels(8.0),
-            Length::ButtonHeight => flood::Length::Pixels(36.0),
-            Length::ButtonTopBottomPadding => flood::Length::Pixels(11.0),
-            Length::ButtonWidth(text) => (flood::Length::TextUnit(text) * flood::Length::Pixels(16.0)) + (flood::Length::Spacing * 3),
         }
     }
 }
\ No newline at end of file
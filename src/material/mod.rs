@@ -1,8 +1,7 @@
 use flood::Flood;
-use traits::Update;
 pub use window::TouchMsg;
 pub use flood::Placement;
-use self::components::button;
+use self::components::{button, nav_track};
 pub use self::components::button::{ButtonStyle, ButtonKind};
 pub use self::color::*;
 pub use self::model::Model;
@@ -51,34 +50,33 @@ pub fn update(model: &mut Model, msg: Msg) {
     match msg {
         Msg::None => {}
         Msg::ButtonMsg(button_id, button_msg) => {
-            let mut button_model = model.get_button_model(button_id);
-            button_model.update(button_msg);
-            model.set_button_model(button_id, button_model);
+            model.button_models.update(button_id, button_msg);
+        }
+        Msg::NavTrackMsg(nav_track_id, nav_track_msg) => {
+            model.nav_track_models.update(nav_track_id, nav_track_msg);
         }
     }
 }
 
 mod model {
     use super::components::button;
-    use std::collections::HashMap;
+    use super::components::nav_track;
+    use store::Store;
     use super::Palette;
 
     #[derive(Clone, PartialEq, Debug, Default)]
     pub struct Model {
-        pub button_models: HashMap<u64, button::ButtonMdl>,
+        pub button_models: Store<button::ButtonMdl>,
+        pub nav_track_models: Store<nav_track::NavTrackMdl>,
         pub palette: Palette,
     }
 
     impl Model {
         pub fn get_button_model(&self, tag: u64) -> button::ButtonMdl {
-            match self.button_models.get(&tag) {
-                Some(model) => *model,
-                None => button::ButtonMdl::default(),
-            }
+            self.button_models.get(tag)
         }
         pub fn set_button_model(&mut self, tag: u64, button_model: button::ButtonMdl) {
-            let button_models = &mut self.button_models;
-            button_models.insert(tag, button_model);
+            self.button_models.set(tag, button_model);
         }
     }
 }
@@ -87,4 +85,5 @@ mod model {
 pub enum Msg {
     None,
     ButtonMsg(u64, button::ButtonMsg),
+    NavTrackMsg(u64, nav_track::NavTrackMsg),
 }
@@ -0,0 +1,96 @@
+use base::Rectangle;
+use flood::{Ink, Path};
+use glium;
+use glium::backend::Facade;
+use glium::Surface;
+use super::layers::{sort_by_approach, Layers};
+use super::model::{clip_bounds, Vertex};
+use super::tessellation::{fill_triangles, flatten, stroke_triangles};
+
+pub struct Canvas<'a> {
+    pub anchor: (f32, f32),
+    pub approach: f32,
+    pub paths: &'a [Path],
+    pub inks: &'a [Ink],
+    pub clip: Option<Rectangle>,
+}
+
+// Tessellates canvas paths into colored triangles drawn with the patch shaders. Inks on
+// one canvas share an approach and blend over earlier inks in the order they are given.
+pub struct CanvasRenderer {
+    pub program: glium::Program,
+    pub vertex_buffer: glium::VertexBuffer<Vertex>,
+    layers: Layers,
+    pub indices: glium::index::NoIndices,
+    modelview: [[f32; 4]; 4],
+    draw_parameters: glium::DrawParameters<'static>,
+}
+
+impl CanvasRenderer {
+    pub fn new<F: Facade>(display: &F, modelview: [[f32; 4]; 4]) -> Self {
+        let vertex_shader_src = include_str!("shaders/patch_vertex_shader.glsl");
+        let fragment_shader_src = include_str!("shaders/patch_fragment_shader.glsl");
+        let program = glium::Program::from_source(display, vertex_shader_src, fragment_shader_src, None).unwrap();
+        let vertex_buffer = glium::VertexBuffer::new(display, &[]).unwrap();
+        let indices = glium::index::NoIndices(glium::index::PrimitiveType::TrianglesList);
+        let draw_parameters = glium::DrawParameters {
+            depth: glium::Depth { test: glium::DepthTest::IfLessOrEqual, write: false, ..Default::default() },
+            blend: glium::Blend::alpha_blending(),
+            smooth: Some(glium::Smooth::Nicest),
+            ..Default::default()
+        };
+        CanvasRenderer { program, vertex_buffer, layers: Layers::default(), indices, modelview, draw_parameters }
+    }
+
+    pub fn set_modelview(&mut self, modelview: [[f32; 4]; 4]) {
+        self.modelview = modelview;
+    }
+
+    pub fn set_canvases<F: Facade>(&mut self, canvases: &[Canvas], display: &F) {
+        let mut canvases = canvases.iter().collect::<Vec<_>>();
+        sort_by_approach(&mut canvases, |canvas| canvas.approach);
+        let mut layers = Layers::default();
+        let mut vertices = Vec::new();
+        for canvas in canvases {
+            let canvas_vertices = canvas.trianglelist();
+            layers.push(canvas.approach, canvas_vertices.len());
+            vertices.extend(canvas_vertices);
+        }
+        self.layers = layers;
+        self.vertex_buffer = glium::VertexBuffer::new(display, &vertices).unwrap();
+    }
+
+    pub fn approaches(&self) -> Vec<f32> {
+        self.layers.approaches()
+    }
+
+    pub fn draw(&self, frame: &mut glium::Frame, approach: f32) {
+        if let Some(range) = self.layers.range(approach) {
+            let uniforms = uniform! { modelview: self.modelview };
+            let vertices = self.vertex_buffer.slice(range).unwrap();
+            frame.draw(vertices, &self.indices, &self.program, &uniforms, &self.draw_parameters).unwrap();
+        }
+    }
+}
+
+impl<'a> Canvas<'a> {
+    fn trianglelist(&self) -> Vec<Vertex> {
+        let (left, top) = self.anchor;
+        let clip = clip_bounds(&self.clip);
+        let subpaths = flatten(self.paths);
+        let mut vertices = Vec::new();
+        for ink in self.inks {
+            let (points, color) = match ink {
+                &Ink::Fill(color) => {
+                    (subpaths.iter().flat_map(|&(ref points, _)| fill_triangles(points)).collect::<Vec<_>>(), color)
+                }
+                &Ink::Stroke(width, color) => {
+                    (subpaths.iter().flat_map(|&(ref points, closed)| stroke_triangles(points, closed, width)).collect::<Vec<_>>(), color)
+                }
+            };
+            let color = color.to_gl();
+            vertices.extend(points.into_iter().map(|(x, y)| Vertex { position: [left + x, top + y, self.approach], color, clip }));
+        }
+        vertices
+    }
+}
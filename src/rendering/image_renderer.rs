@@ -0,0 +1,181 @@
+use base::{ImageSource, Rectangle};
+use Color;
+use flood::Fit;
+use glium;
+use glium::backend::Facade;
+use glium::Surface;
+use std::collections::HashMap;
+use std::mem;
+use super::layers::{merge_approaches, sort_by_approach, Layers};
+use super::model::{clip_bounds, ImageVertex};
+
+pub struct Image {
+    pub source: ImageSource,
+    pub fit: Fit,
+    pub tint: Color,
+    pub anchor: (f32, f32),
+    pub width: f32,
+    pub height: f32,
+    pub approach: f32,
+    pub clip: Option<Rectangle>,
+}
+
+struct ImageTexture {
+    texture: glium::texture::Texture2d,
+    vertex_buffer: glium::VertexBuffer<ImageVertex>,
+    layers: Layers,
+}
+
+// Keeps one texture per image source. Sources that fail to load are remembered as
+// missing so they are not decoded again on every frame.
+pub struct ImageRenderer {
+    pub program: glium::Program,
+    pub indices: glium::index::NoIndices,
+    textures: HashMap<ImageSource, Option<ImageTexture>>,
+    order: Vec<ImageSource>,
+    modelview: [[f32; 4]; 4],
+    draw_parameters: glium::DrawParameters<'static>,
+}
+
+impl ImageRenderer {
+    pub fn new<F: Facade>(display: &F, modelview: [[f32; 4]; 4]) -> Self {
+        let vertex_shader_src = include_str!("shaders/image_vertex_shader.glsl");
+        let fragment_shader_src = include_str!("shaders/image_fragment_shader.glsl");
+        let program = glium::Program::from_source(display, vertex_shader_src, fragment_shader_src, None).unwrap();
+        let indices = glium::index::NoIndices(glium::index::PrimitiveType::TrianglesList);
+        let draw_parameters = glium::DrawParameters {
+            depth: glium::Depth { test: glium::DepthTest::IfLessOrEqual, write: false, ..Default::default() },
+            blend: glium::Blend::alpha_blending(),
+            smooth: Some(glium::Smooth::Nicest),
+            ..Default::default()
+        };
+        ImageRenderer { program, indices, textures: HashMap::new(), order: Vec::new(), modelview, draw_parameters }
+    }
+
+    pub fn set_modelview(&mut self, modelview: [[f32; 4]; 4]) {
+        self.modelview = modelview;
+    }
+
+    pub fn set_images<F: Facade>(&mut self, images: &[Image], display: &F) {
+        let mut stale_textures = mem::replace(&mut self.textures, HashMap::new());
+        let mut vertices: HashMap<ImageSource, (Vec<ImageVertex>, Layers)> = HashMap::new();
+        let mut order = Vec::new();
+        let mut images = images.iter().collect::<Vec<_>>();
+        sort_by_approach(&mut images, |image| image.approach);
+        for image in images {
+            if !self.textures.contains_key(&image.source) {
+                let texture = match stale_textures.remove(&image.source) {
+                    Some(texture) => texture,
+                    None => load_texture(&image.source, display),
+                };
+                self.textures.insert(image.source.clone(), texture);
+                order.push(image.source.clone());
+            }
+            if let Some(&Some(ref texture)) = self.textures.get(&image.source) {
+                let dimensions = (texture.texture.width() as f32, texture.texture.height() as f32);
+                let image_vertices = image.trianglelist(dimensions);
+                let &mut (ref mut source_vertices, ref mut layers) = vertices.entry(image.source.clone()).or_insert_with(Default::default);
+                layers.push(image.approach, image_vertices.len());
+                source_vertices.extend(image_vertices);
+            }
+        }
+        for (source, texture) in self.textures.iter_mut() {
+            if let &mut Some(ref mut texture) = texture {
+                let (source_vertices, layers) = vertices.remove(source).unwrap_or_default();
+                texture.vertex_buffer = glium::VertexBuffer::new(display, &source_vertices).unwrap();
+                texture.layers = layers;
+            }
+        }
+        self.order = order;
+    }
+
+    pub fn approaches(&self) -> Vec<f32> {
+        merge_approaches(self.textures.values()
+            .filter_map(|texture| texture.as_ref().map(|texture| texture.layers.approaches()))
+            .collect())
+    }
+
+    pub fn draw(&self, frame: &mut glium::Frame, approach: f32) {
+        for source in &self.order {
+            if let Some(&Some(ref texture)) = self.textures.get(source) {
+                let range = match texture.layers.range(approach) {
+                    Some(range) => range,
+                    None => continue,
+                };
+                let sampler = texture.texture.sampled()
+                    .magnify_filter(glium::uniforms::MagnifySamplerFilter::Linear)
+                    .minify_filter(glium::uniforms::MinifySamplerFilter::Linear);
+                let uniforms = uniform! { tex: sampler, modelview: self.modelview };
+                let vertices = texture.vertex_buffer.slice(range).unwrap();
+                frame.draw(vertices, &self.indices, &self.program, &uniforms, &self.draw_parameters).unwrap();
+            }
+        }
+    }
+}
+
+fn load_texture<F: Facade>(source: &ImageSource, display: &F) -> Option<ImageTexture> {
+    match source.load() {
+        Ok(bitmap) => {
+            let raw = glium::texture::RawImage2d::from_raw_rgba(bitmap.rgba.clone(), (bitmap.width, bitmap.height));
+            let texture = glium::texture::Texture2d::new(display, raw).unwrap();
+            let vertex_buffer = glium::VertexBuffer::new(display, &[]).unwrap();
+            Some(ImageTexture { texture, vertex_buffer, layers: Layers::default() })
+        }
+        Err(error) => {
+            println!("Error loading {:?}: {}", source, error);
+            None
+        }
+    }
+}
+
+impl Image {
+    fn trianglelist(&self, image_dimensions: (f32, f32)) -> Vec<ImageVertex> {
+        let (x, y) = self.anchor;
+        let ([left, top, right, bottom], [u_left, v_top, u_right, v_bottom]) =
+            fit_rect(self.fit, (x, y, self.width, self.height), image_dimensions);
+        let (z, tint, clip) = (self.approach, self.tint.to_gl(), clip_bounds(&self.clip));
+        let vertex = |x: f32, y: f32, u: f32, v: f32| ImageVertex { position: [x, y, z], tex_coords: [u, v], tint, clip };
+        let lt_vertex = vertex(left, top, u_left, v_top);
+        let rt_vertex = vertex(right, top, u_right, v_top);
+        let rb_vertex = vertex(right, bottom, u_right, v_bottom);
+        let lb_vertex = vertex(left, bottom, u_left, v_bottom);
+        vec![lt_vertex, rt_vertex, lb_vertex, lb_vertex, rt_vertex, rb_vertex]
+    }
+}
+
+// Places an image inside a block and crops it to the block. Returns the drawn rectangle
+// and its texture coordinates, both as left, top, right, bottom.
+fn fit_rect(fit: Fit, (left, top, width, height): (f32, f32, f32, f32), (image_width, image_height): (f32, f32)) -> ([f32; 4], [f32; 4]) {
+    let (x_scale, y_scale) = (width / image_width, height / image_height);
+    let (scaled_width, scaled_height) = match fit {
+        Fit::Fill => (width, height),
+        Fit::Contain => (image_width * x_scale.min(y_scale), image_height * x_scale.min(y_scale)),
+        Fit::Cover => (image_width * x_scale.max(y_scale), image_height * x_scale.max(y_scale)),
+        Fit::Center => (image_width, image_height),
+    };
+    let (image_left, image_top) = (left + (width - scaled_width) / 2.0, top + (height - scaled_height) / 2.0);
+    let (drawn_left, drawn_top) = (image_left.max(left), image_top.max(top));
+    let (drawn_right, drawn_bottom) = ((image_left + scaled_width).min(left + width), (image_top + scaled_height).min(top + height));
+    let u = |x: f32| (x - image_left) / scaled_width;
+    let v = |y: f32| (y - image_top) / scaled_height;
+    ([drawn_left, drawn_top, drawn_right, drawn_bottom], [u(drawn_left), v(drawn_top), u(drawn_right), v(drawn_bottom)])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn contain_letterboxes_wide_image() {
+        let (rect, uv) = fit_rect(Fit::Contain, (0.0, 0.0, 100.0, 100.0), (200.0, 100.0));
+        assert_eq!([0.0, 25.0, 100.0, 75.0], rect);
+        assert_eq!([0.0, 0.0, 1.0, 1.0], uv);
+    }
+
+    #[test]
+    fn cover_crops_wide_image() {
+        let (rect, uv) = fit_rect(Fit::Cover, (0.0, 0.0, 100.0, 100.0), (200.0, 100.0));
+        assert_eq!([0.0, 0.0, 100.0, 100.0], rect);
+        assert_eq!([0.25, 0.0, 0.75, 1.0], uv);
+    }
+}
@@ -0,0 +1,70 @@
+use std::cmp::Ordering;
+use std::ops::Range;
+
+// Runs of vertices that share an approach, from back to front. Blending renderers keep
+// one per vertex buffer so the screen can draw every renderer one approach at a time.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Layers {
+    runs: Vec<(f32, Range<usize>)>,
+}
+
+impl Layers {
+    // Adds the next count vertices at the given approach. Approaches must not decrease.
+    pub fn push(&mut self, approach: f32, count: usize) {
+        if count == 0 {
+            return;
+        }
+        let end = self.runs.last().map_or(0, |&(_, ref range)| range.end);
+        if let Some(&mut (last_approach, ref mut range)) = self.runs.last_mut() {
+            if last_approach == approach {
+                range.end += count;
+                return;
+            }
+        }
+        self.runs.push((approach, end..end + count));
+    }
+
+    pub fn approaches(&self) -> Vec<f32> {
+        self.runs.iter().map(|&(approach, _)| approach).collect()
+    }
+
+    pub fn range(&self, approach: f32) -> Option<Range<usize>> {
+        self.runs.iter()
+            .find(|&&(run_approach, _)| run_approach == approach)
+            .map(|&(_, ref range)| range.clone())
+    }
+}
+
+// Sorts items back to front, keeping the given order among items at the same approach.
+pub fn sort_by_approach<T, F>(items: &mut Vec<T>, approach: F) where
+    F: Fn(&T) -> f32
+{
+    items.sort_by(|a, b| approach(a).partial_cmp(&approach(b)).unwrap_or(Ordering::Equal));
+}
+
+// Every approach in any of the given approach lists, from back to front.
+pub fn merge_approaches(approaches: Vec<Vec<f32>>) -> Vec<f32> {
+    let mut merged = approaches.into_iter().flat_map(|approaches| approaches).collect::<Vec<_>>();
+    sort_by_approach(&mut merged, |&approach| approach);
+    merged.dedup();
+    merged
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn layers_join_runs_at_the_same_approach() {
+        let mut layers = Layers::default();
+        layers.push(0.0, 6);
+        layers.push(0.0, 6);
+        layers.push(1.0, 0);
+        layers.push(2.0, 3);
+        assert_eq!(vec![0.0, 2.0], layers.approaches());
+        assert_eq!(Some(0..12), layers.range(0.0));
+        assert_eq!(Some(12..15), layers.range(2.0));
+        assert_eq!(None, layers.range(1.0));
+        assert_eq!(vec![0.0, 1.0, 2.0], merge_approaches(vec![vec![0.0, 2.0], vec![1.0, 2.0]]));
+    }
+}
@@ -0,0 +1,25 @@
+use Color;
+
+// Light coordinates are fractions of the screen height, except light_x which is a
+// fraction of the screen width. Negative light_y places the light above the screen.
+// Softness widens every shadow's blur; zero gives the Material defaults.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct Lighting {
+    pub light_x: f32,
+    pub light_y: f32,
+    pub light_height: f32,
+    pub shadow_color: Color,
+    pub softness: f32,
+}
+
+impl Default for Lighting {
+    fn default() -> Self {
+        Lighting {
+            light_x: 0.5,
+            light_y: -0.125,
+            light_height: 1.5,
+            shadow_color: Color::black(),
+            softness: 0.0,
+        }
+    }
+}
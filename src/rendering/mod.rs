@@ -1,6 +1,17 @@
+pub use self::canvas_renderer::{Canvas, CanvasRenderer};
+pub use self::image_renderer::{Image, ImageRenderer};
+pub use self::layers::{merge_approaches, sort_by_approach, Layers};
+pub use self::lighting::Lighting;
 pub use self::patch_renderer::PatchRenderer;
 pub use self::shadow_renderer::ShadowRenderer;
+pub use self::surface_renderer::SurfaceRenderer;
 
 pub mod model;
+mod canvas_renderer;
+mod image_renderer;
+mod layers;
+mod lighting;
 mod patch_renderer;
 mod shadow_renderer;
+mod surface_renderer;
+mod tessellation;
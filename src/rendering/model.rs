@@ -1,5 +1,7 @@
 use cage::Cage;
+use base::Rectangle;
 use Color;
+use flood::Gradient;
 use parser;
 use xml;
 
@@ -44,11 +46,33 @@ impl Patchwork {
 pub struct Patch {
     pub cage: Cage,
     pub color: Color,
+    pub gradient: Option<Gradient>,
+    pub clip: Option<Rectangle>,
 }
 
 impl Patch {
     pub fn new((x, y): (f32, f32), width: f32, height: f32, z: f32, color: Color) -> Self {
-        Patch { cage: Cage::from((x, x + width, y + height, y, z, z)), color }
+        Patch { cage: Cage::from((x, x + width, y + height, y, z, z)), color, gradient: None, clip: None }
+    }
+    // The patch color stands in for the gradient wherever a single color is needed, such as
+    // when casting shadows.
+    pub fn with_gradient((x, y): (f32, f32), width: f32, height: f32, z: f32, gradient: Gradient) -> Self {
+        let alpha = gradient.stops().iter().fold(0.0f32, |alpha, &(_, color)| alpha.max(color.a));
+        let color = Color { a: alpha, ..gradient.color_at(0.5) };
+        Patch { cage: Cage::from((x, x + width, y + height, y, z, z)), color, gradient: Some(gradient), clip: None }
+    }
+    pub fn with_clip(mut self, clip: Option<Rectangle>) -> Self {
+        self.clip = clip;
+        self
+    }
+    pub fn clip_bounds(&self) -> [f32; 4] {
+        clip_bounds(&self.clip)
+    }
+    pub fn is_opaque(&self) -> bool {
+        match self.gradient {
+            Some(ref gradient) => gradient.is_opaque(),
+            None => self.color.a >= 1.0,
+        }
     }
     pub fn from_attributes(attributes: &Vec<xml::attribute::OwnedAttribute>) -> Self {
         let mut patch = Patch { ..Default::default() };
@@ -62,8 +86,56 @@ impl Patch {
     }
 }
 
+// Fragment shaders discard anything outside a vertex's clip, given as left, top, right, bottom.
+pub const UNCLIPPED: [f32; 4] = [-1.0e6, -1.0e6, 1.0e6, 1.0e6];
+
+pub fn clip_bounds(clip: &Option<Rectangle>) -> [f32; 4] {
+    clip.map_or(UNCLIPPED, |clip| clip.to_gl())
+}
+
 #[derive(Copy, Clone)]
 pub struct Vertex {
     pub position: [f32; 3],
+    pub color: [f32; 4],
+    pub clip: [f32; 4],
+}
+implement_vertex!(Vertex, position, color, clip);
+
+#[derive(Copy, Clone)]
+pub struct ShadowVertex {
+    pub position: [f32; 3],
+    pub color: [f32; 4],
+    pub caster: [f32; 4],
+    pub corners: [f32; 4],
+    pub sigma: f32,
+    pub clip: [f32; 4],
+}
+implement_vertex!(ShadowVertex, position, color, caster, corners, sigma, clip);
+
+pub struct Surface {
+    pub patch: Patch,
+    pub corners: [f32; 4],
+    pub border_width: f32,
+    pub border_color: Color,
+}
+
+#[derive(Copy, Clone)]
+pub struct SurfaceVertex {
+    pub position: [f32; 3],
+    pub color: [f32; 4],
+    pub border_color: [f32; 4],
+    pub rect: [f32; 4],
+    pub corners: [f32; 4],
+    pub border_width: f32,
+    pub clip: [f32; 4],
+}
+implement_vertex!(SurfaceVertex, position, color, border_color, rect, corners, border_width, clip);
+
+#[derive(Copy, Clone)]
+pub struct ImageVertex {
+    pub position: [f32; 3],
+    pub tex_coords: [f32; 2],
+    pub tint: [f32; 4],
+    pub clip: [f32; 4],
 }
-implement_vertex!(Vertex, position);
+implement_vertex!(ImageVertex, position, tex_coords, tint, clip);
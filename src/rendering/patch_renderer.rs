@@ -1,16 +1,23 @@
-use Color;
 use glium;
 use glium::backend::Facade;
 use glium::Surface;
+use flood::Gradient;
+use std::f32::consts::PI;
+use super::layers::{sort_by_approach, Layers};
 use super::model::{Patch, Vertex};
+use super::tessellation::{clip_polygon, fan};
+
+const RADIAL_SECTORS: usize = 64;
 
 pub struct PatchRenderer {
     pub program: glium::Program,
     pub vertex_buffer: glium::VertexBuffer<Vertex>,
+    pub translucent_vertex_buffer: glium::VertexBuffer<Vertex>,
+    translucent_layers: Layers,
     pub indices: glium::index::NoIndices,
     modelview: [[f32; 4]; 4],
-    color: [f32; 4],
     draw_parameters: glium::DrawParameters<'static>,
+    translucent_draw_parameters: glium::DrawParameters<'static>,
 }
 
 impl PatchRenderer {
@@ -18,45 +25,171 @@ impl PatchRenderer {
         let vertex_shader_src = include_str!("shaders/patch_vertex_shader.glsl");
         let fragment_shader_src = include_str!("shaders/patch_fragment_shader.glsl");
         let program = glium::Program::from_source(display, vertex_shader_src, fragment_shader_src, None).unwrap();
-        let vertex_buffer = glium::VertexBuffer::empty_dynamic(
-            display,
-            SURFACE_TRIANGLELIST_VERTEX_COUNT).unwrap();
+        let vertex_buffer = glium::VertexBuffer::new(display, &[]).unwrap();
+        let translucent_vertex_buffer = glium::VertexBuffer::new(display, &[]).unwrap();
         let indices = glium::index::NoIndices(glium::index::PrimitiveType::TrianglesList);
-        let color = Color::white().to_gl();
         let draw_parameters = glium::DrawParameters {
-            depth: glium::Depth { test: glium::DepthTest::IfLess, write: true, ..Default::default() },
+            depth: glium::Depth { test: glium::DepthTest::IfLessOrEqual, write: true, ..Default::default() },
+            smooth: Some(glium::Smooth::Nicest),
+            ..Default::default()
+        };
+        let translucent_draw_parameters = glium::DrawParameters {
+            depth: glium::Depth { test: glium::DepthTest::IfLessOrEqual, write: false, ..Default::default() },
+            blend: glium::Blend::alpha_blending(),
             smooth: Some(glium::Smooth::Nicest),
             ..Default::default()
         };
-        PatchRenderer { program, vertex_buffer, indices, modelview, color, draw_parameters }
+        PatchRenderer {
+            program,
+            vertex_buffer,
+            translucent_vertex_buffer,
+            translucent_layers: Layers::default(),
+            indices,
+            modelview,
+            draw_parameters,
+            translucent_draw_parameters,
+        }
     }
 
     pub fn set_modelview(&mut self, modelview: [[f32; 4]; 4]) {
         self.modelview = modelview;
     }
 
-    pub fn set_patch(&mut self, patch: &Patch) {
-        self.vertex_buffer.write(&patch.surface_trianglelist());
-        self.color = patch.color.to_gl();
+    // Opaque patches go through the depth buffer in any order. Translucent patches are
+    // blended back-to-front, keeping the given order among patches at the same approach.
+    pub fn set_patches<F: Facade>(&mut self, patches: &[Patch], display: &F) {
+        let vertices = patches.iter()
+            .filter(|patch| patch.color.a > 0.0 && patch.is_opaque())
+            .flat_map(|patch| patch.surface_trianglelist())
+            .collect::<Vec<_>>();
+        self.vertex_buffer = glium::VertexBuffer::new(display, &vertices).unwrap();
+
+        let mut translucent_patches = patches.iter()
+            .filter(|patch| patch.color.a > 0.0 && !patch.is_opaque())
+            .collect::<Vec<_>>();
+        sort_by_approach(&mut translucent_patches, |patch| patch.approach());
+        let mut translucent_layers = Layers::default();
+        let mut translucent_vertices = Vec::new();
+        for patch in translucent_patches {
+            let vertices = patch.surface_trianglelist();
+            translucent_layers.push(patch.approach(), vertices.len());
+            translucent_vertices.extend(vertices);
+        }
+        self.translucent_layers = translucent_layers;
+        self.translucent_vertex_buffer = glium::VertexBuffer::new(display, &translucent_vertices).unwrap();
     }
 
-    pub fn draw(&self, frame: &mut glium::Frame) {
-        if self.color[3] > 0.0 {
-            let uniforms = uniform! { modelview: self.modelview, uniformcolor: self.color };
+    pub fn draw_opaque(&self, frame: &mut glium::Frame) {
+        if self.vertex_buffer.len() > 0 {
+            let uniforms = uniform! { modelview: self.modelview };
             frame.draw(&self.vertex_buffer, &self.indices, &self.program, &uniforms, &self.draw_parameters).unwrap();
         }
     }
-}
 
-const SURFACE_TRIANGLELIST_VERTEX_COUNT: usize = 6;
+    pub fn translucent_approaches(&self) -> Vec<f32> {
+        self.translucent_layers.approaches()
+    }
+
+    pub fn draw_translucent(&self, frame: &mut glium::Frame, approach: f32) {
+        if let Some(range) = self.translucent_layers.range(approach) {
+            let uniforms = uniform! { modelview: self.modelview };
+            let vertices = self.translucent_vertex_buffer.slice(range).unwrap();
+            frame.draw(vertices, &self.indices, &self.program, &uniforms, &self.translucent_draw_parameters).unwrap();
+        }
+    }
+}
 
 impl Patch {
+    fn approach(&self) -> f32 {
+        let (_, _, _, _, far, _) = self.cage.limits();
+        far
+    }
+
     fn surface_trianglelist(&self) -> Vec<Vertex> {
         let (left, right, bottom, top, far, _) = self.cage.limits();
-        let lt_vertex = Vertex { position: [left, top, far] };
-        let rt_vertex = Vertex { position: [right, top, far] };
-        let rb_vertex = Vertex { position: [right, bottom, far] };
-        let lb_vertex = Vertex { position: [left, bottom, far] };
+        let clip = self.clip_bounds();
+        if let Some(ref gradient) = self.gradient {
+            return gradient_trianglelist(gradient, (left, top, right, bottom), far, clip);
+        }
+        let color = self.color.to_gl();
+        let lt_vertex = Vertex { position: [left, top, far], color, clip };
+        let rt_vertex = Vertex { position: [right, top, far], color, clip };
+        let rb_vertex = Vertex { position: [right, bottom, far], color, clip };
+        let lb_vertex = Vertex { position: [left, bottom, far], color, clip };
         vec![lt_vertex, rt_vertex, lb_vertex, lb_vertex, rt_vertex, rb_vertex]
     }
 }
+
+// Gradients are cut into bands that end at each color stop so the per-vertex colors
+// interpolate between neighboring stops. Linear bands are exact; radial bands are split
+// into narrow sectors.
+fn gradient_trianglelist(gradient: &Gradient, rect: (f32, f32, f32, f32), z: f32, clip: [f32; 4]) -> Vec<Vertex> {
+    let (left, top, right, bottom) = rect;
+    if right <= left || bottom <= top {
+        return Vec::new();
+    }
+    let corners = [(left, top), (right, top), (right, bottom), (left, bottom)];
+    let polygons = match gradient {
+        &Gradient::Linear(angle, _) => {
+            let radians = angle.to_radians();
+            let (direction_x, direction_y) = (radians.sin(), -radians.cos());
+            let (width, height) = (right - left, bottom - top);
+            let length = (width * direction_x).abs() + (height * direction_y).abs();
+            let center = ((left + right) / 2.0, (top + bottom) / 2.0);
+            let position = |(x, y): (f32, f32)| ((x - center.0) * direction_x + (y - center.1) * direction_y) / length + 0.5;
+            let point = |at: f32, side: f32| {
+                let along = (at - 0.5) * length;
+                (center.0 + direction_x * along - direction_y * side, center.1 + direction_y * along + direction_x * side)
+            };
+            let reach = width + height;
+            let bands = band_edges(gradient, &corners.iter().map(|&corner| position(corner)).collect::<Vec<_>>());
+            bands.windows(2)
+                .map(|band| vec![point(band[0], -reach), point(band[1], -reach), point(band[1], reach), point(band[0], reach)])
+                .map(|polygon| clip_polygon(polygon, rect).into_iter().map(|vertex| (vertex, position(vertex))).collect::<Vec<_>>())
+                .collect::<Vec<_>>()
+        }
+        &Gradient::Radial((center_x, center_y), radius, _) => {
+            let center = (left + (right - left) * center_x, top + (bottom - top) * center_y);
+            let farthest = corners.iter()
+                .map(|&(x, y)| ((x - center.0).powi(2) + (y - center.1).powi(2)).sqrt())
+                .fold(0.0f32, f32::max);
+            let radius = (farthest * radius).max(0.001);
+            let position = |(x, y): (f32, f32)| ((x - center.0).powi(2) + (y - center.1).powi(2)).sqrt() / radius;
+            let mut bands = band_edges(gradient, &[0.0, farthest / radius]);
+            // Chords of the outer ring must still reach the corners.
+            if let Some(last) = bands.last_mut() {
+                *last /= (PI / RADIAL_SECTORS as f32).cos();
+            }
+            let point = |at: f32, angle: f32| (center.0 + angle.cos() * at * radius, center.1 + angle.sin() * at * radius);
+            let mut polygons = Vec::new();
+            for sector in 0..RADIAL_SECTORS {
+                let (start, end) = (sector as f32 * 2.0 * PI / RADIAL_SECTORS as f32, (sector + 1) as f32 * 2.0 * PI / RADIAL_SECTORS as f32);
+                for band in bands.windows(2) {
+                    let polygon = if band[0] == 0.0 {
+                        vec![center, point(band[1], start), point(band[1], end)]
+                    } else {
+                        vec![point(band[0], start), point(band[1], start), point(band[1], end), point(band[0], end)]
+                    };
+                    let polygon = clip_polygon(polygon, rect);
+                    polygons.push(polygon.into_iter().map(|vertex| (vertex, position(vertex))).collect::<Vec<_>>());
+                }
+            }
+            polygons
+        }
+    };
+    polygons.iter()
+        .flat_map(|polygon| fan(polygon))
+        .map(|((x, y), at)| Vertex { position: [x, y, z], color: gradient.color_at(at).to_gl(), clip })
+        .collect()
+}
+
+// Gradient positions that start and end a band, spanning the positions of the given
+// points so the bands cover the whole patch.
+fn band_edges(gradient: &Gradient, covered: &[f32]) -> Vec<f32> {
+    let low = covered.iter().cloned().fold(f32::INFINITY, f32::min);
+    let high = covered.iter().cloned().fold(f32::NEG_INFINITY, f32::max);
+    let mut edges = vec![low];
+    edges.extend(gradient.stops().iter().map(|&(stop, _)| stop).filter(|&stop| stop > low && stop < high));
+    edges.push(high);
+    edges
+}
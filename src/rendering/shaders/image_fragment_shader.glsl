@@ -0,0 +1,14 @@
+#version 140
+
+uniform sampler2D tex;
+in vec2 v_point;
+in vec2 v_tex_coords;
+in vec4 v_tint;
+in vec4 v_clip;
+out vec4 color;
+
+void main() {
+    if (v_point.x < v_clip.x || v_point.y < v_clip.y || v_point.x >= v_clip.z || v_point.y >= v_clip.w) discard;
+    color = texture(tex, v_tex_coords) * v_tint;
+    if (color.a < 0.01) discard;
+}
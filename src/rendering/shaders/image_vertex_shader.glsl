@@ -0,0 +1,19 @@
+#version 140
+
+in vec3 position;
+in vec2 tex_coords;
+in vec4 tint;
+in vec4 clip;
+out vec2 v_point;
+out vec2 v_tex_coords;
+out vec4 v_tint;
+out vec4 v_clip;
+uniform mat4 modelview;
+
+void main() {
+    gl_Position = modelview * vec4(position, 1.0);
+    v_point = position.xy;
+    v_tex_coords = tex_coords;
+    v_tint = tint;
+    v_clip = clip;
+}
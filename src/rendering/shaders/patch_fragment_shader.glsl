@@ -1,8 +1,11 @@
 #version 140
 
+in vec2 v_point;
+in vec4 v_color;
+in vec4 v_clip;
 out vec4 color;
-uniform vec4 uniformcolor;
 
 void main() {
-    color = uniformcolor;
+    if (v_point.x < v_clip.x || v_point.y < v_clip.y || v_point.x >= v_clip.z || v_point.y >= v_clip.w) discard;
+    color = v_color;
 }
@@ -1,8 +1,16 @@
 #version 140
 
 in vec3 position;
+in vec4 color;
+in vec4 clip;
+out vec2 v_point;
+out vec4 v_color;
+out vec4 v_clip;
 uniform mat4 modelview;
 
 void main() {
     gl_Position = modelview * vec4(position, 1.0);
-}
\ No newline at end of file
+    v_point = position.xy;
+    v_color = color;
+    v_clip = clip;
+}
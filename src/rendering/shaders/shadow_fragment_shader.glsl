@@ -0,0 +1,72 @@
+#version 140
+
+in vec2 v_point;
+in vec4 v_color;
+in vec4 v_caster;
+in vec4 v_corners;
+in float v_sigma;
+in vec4 v_clip;
+out vec4 color;
+
+const int ROUNDED_SAMPLES = 4;
+
+// Approximates erf to within 5e-4, which is plenty for an 8-bit shadow.
+vec4 erf(vec4 x) {
+    vec4 s = sign(x);
+    vec4 a = abs(x);
+    x = 1.0 + (0.278393 + (0.230389 + 0.078108 * (a * a)) * a) * a;
+    x *= x;
+    return s - s / (x * x);
+}
+
+float gaussian(float x, float sigma) {
+    return exp(-(x * x) / (2.0 * sigma * sigma)) / (sqrt(2.0 * 3.14159265) * sigma);
+}
+
+// Coverage of the caster rectangle convolved with a Gaussian of deviation sigma.
+float blurred_box(vec2 lower, vec2 upper, vec2 point, float sigma) {
+    vec4 query = vec4(point - lower, point - upper);
+    vec4 integral = 0.5 + 0.5 * erf(query * (sqrt(0.5) / sigma));
+    return (integral.z - integral.x) * (integral.w - integral.y);
+}
+
+// Horizontal coverage of one row of a rounded box, blurred along the row. The row is
+// narrowed where it crosses the corners.
+float blurred_row(float x, float y, float sigma, float radius, vec2 half_size) {
+    float delta = min(half_size.y - radius - abs(y), 0.0);
+    float curved = half_size.x - radius + sqrt(max(0.0, radius * radius - delta * delta));
+    vec4 integral = 0.5 + 0.5 * erf(vec4(x + curved, x - curved, 0.0, 0.0) * (sqrt(0.5) / sigma));
+    return integral.x - integral.y;
+}
+
+// Coverage of a rounded caster, summing blurred rows over the few deviations of the
+// Gaussian that reach the point.
+float blurred_rounded_box(vec2 lower, vec2 upper, vec2 point, float sigma, float radius) {
+    vec2 half_size = (upper - lower) * 0.5;
+    vec2 offset = point - (lower + upper) * 0.5;
+    float start = clamp(-3.0 * sigma, offset.y - half_size.y, offset.y + half_size.y);
+    float end = clamp(3.0 * sigma, offset.y - half_size.y, offset.y + half_size.y);
+    float step = (end - start) / float(ROUNDED_SAMPLES);
+    float y = start + step * 0.5;
+    float coverage = 0.0;
+    for (int i = 0; i < ROUNDED_SAMPLES; i++) {
+        coverage += blurred_row(offset.x, offset.y - y, sigma, radius, half_size) * gaussian(y, sigma) * step;
+        y += step;
+    }
+    return coverage;
+}
+
+void main() {
+    if (v_point.x < v_clip.x || v_point.y < v_clip.y || v_point.x >= v_clip.z || v_point.y >= v_clip.w) discard;
+    vec2 offset = v_point - (v_caster.xy + v_caster.zw) * 0.5;
+    vec2 half_size = (v_caster.zw - v_caster.xy) * 0.5;
+    // Corners run clockwise from the top left and y grows downward.
+    float radius = offset.x < 0.0
+        ? (offset.y < 0.0 ? v_corners.x : v_corners.w)
+        : (offset.y < 0.0 ? v_corners.y : v_corners.z);
+    radius = clamp(radius, 0.0, min(half_size.x, half_size.y));
+    float coverage = radius > 0.0
+        ? blurred_rounded_box(v_caster.xy, v_caster.zw, v_point, v_sigma, radius)
+        : blurred_box(v_caster.xy, v_caster.zw, v_point, v_sigma);
+    color = vec4(v_color.rgb, v_color.a * coverage);
+}
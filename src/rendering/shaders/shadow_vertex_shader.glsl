@@ -0,0 +1,25 @@
+#version 140
+
+in vec3 position;
+in vec4 color;
+in vec4 caster;
+in vec4 corners;
+in float sigma;
+in vec4 clip;
+out vec2 v_point;
+out vec4 v_color;
+out vec4 v_caster;
+out vec4 v_corners;
+out float v_sigma;
+out vec4 v_clip;
+uniform mat4 modelview;
+
+void main() {
+    gl_Position = modelview * vec4(position, 1.0);
+    v_point = position.xy;
+    v_color = color;
+    v_caster = caster;
+    v_corners = corners;
+    v_sigma = sigma;
+    v_clip = clip;
+}
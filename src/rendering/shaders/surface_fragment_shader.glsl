@@ -0,0 +1,34 @@
+#version 140
+
+in vec2 v_point;
+in vec4 v_color;
+in vec4 v_border_color;
+in vec4 v_rect;
+in vec4 v_corners;
+in float v_border_width;
+in vec4 v_clip;
+out vec4 color;
+
+// Signed distance to a rounded box, negative inside.
+float rounded_box(vec2 offset, vec2 half_size, float radius) {
+    vec2 q = abs(offset) - half_size + radius;
+    return min(max(q.x, q.y), 0.0) + length(max(q, 0.0)) - radius;
+}
+
+void main() {
+    vec2 center = (v_rect.xy + v_rect.zw) * 0.5;
+    vec2 half_size = (v_rect.zw - v_rect.xy) * 0.5;
+    vec2 offset = v_point - center;
+    // Corners run clockwise from the top left and y grows downward.
+    float radius = offset.x < 0.0
+        ? (offset.y < 0.0 ? v_corners.x : v_corners.w)
+        : (offset.y < 0.0 ? v_corners.y : v_corners.z);
+    float distance = rounded_box(offset, half_size, radius);
+    float smoothing = max(0.5 * fwidth(distance), 0.001);
+    float coverage = 1.0 - smoothstep(-smoothing, smoothing, distance);
+    float fill = v_border_width > 0.0 ? 1.0 - smoothstep(-smoothing, smoothing, distance + v_border_width) : 1.0;
+    vec4 surface = mix(v_border_color, v_color, fill);
+    color = vec4(surface.rgb, surface.a * coverage);
+    if (v_point.x < v_clip.x || v_point.y < v_clip.y || v_point.x >= v_clip.z || v_point.y >= v_clip.w) discard;
+    if (color.a < 0.01) discard;
+}
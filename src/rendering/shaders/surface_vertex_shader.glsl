@@ -0,0 +1,28 @@
+#version 140
+
+in vec3 position;
+in vec4 color;
+in vec4 border_color;
+in vec4 rect;
+in vec4 corners;
+in float border_width;
+in vec4 clip;
+out vec2 v_point;
+out vec4 v_color;
+out vec4 v_border_color;
+out vec4 v_rect;
+out vec4 v_corners;
+out float v_border_width;
+out vec4 v_clip;
+uniform mat4 modelview;
+
+void main() {
+    gl_Position = modelview * vec4(position, 1.0);
+    v_point = position.xy;
+    v_color = color;
+    v_border_color = border_color;
+    v_rect = rect;
+    v_corners = corners;
+    v_border_width = border_width;
+    v_clip = clip;
+}
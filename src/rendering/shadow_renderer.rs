@@ -1,148 +1,135 @@
-use Color;
 use glium;
 use glium::backend::Facade;
 use glium::Surface;
-use super::model::{Patch, Vertex};
+use screen::MAX_APPROACH;
+use super::Lighting;
+use super::layers::{sort_by_approach, Layers};
+use super::model::{Patch, ShadowVertex};
+
+const KEY_SHADOW_ALPHA: f32 = 0.24;
+const AMBIENT_SHADOW_ALPHA: f32 = 0.12;
+const MIN_SHADOW_SIGMA: f32 = 0.5;
+// Shadows sit just under their caster so they darken whatever lies beneath it.
+const SHADOW_DROP: f32 = 0.0005;
 
 pub struct ShadowRenderer {
     pub program: glium::Program,
-    pub vertex_buffer: glium::VertexBuffer<Vertex>,
+    pub vertex_buffer: glium::VertexBuffer<ShadowVertex>,
+    layers: Layers,
     pub indices: glium::index::NoIndices,
     modelview: [[f32; 4]; 4],
-    surface_alpha: f32,
-    shadow_color: [f32; 4],
+    lighting: Lighting,
     draw_parameters: glium::DrawParameters<'static>,
 }
 
 impl ShadowRenderer {
     pub fn new<F: Facade>(display: &F, modelview: [[f32; 4]; 4]) -> Self {
-        let vertex_shader_src = include_str!("shaders/patch_vertex_shader.glsl");
-        let fragment_shader_src = include_str!("shaders/patch_fragment_shader.glsl");
+        let vertex_shader_src = include_str!("shaders/shadow_vertex_shader.glsl");
+        let fragment_shader_src = include_str!("shaders/shadow_fragment_shader.glsl");
         let program = glium::Program::from_source(display, vertex_shader_src, fragment_shader_src, None).unwrap();
-        let vertex_buffer = glium::VertexBuffer::empty_dynamic(
-            display,
-            SHADOW_TRIANGLELIST_VERTEX_COUNT).unwrap();
+        let vertex_buffer = glium::VertexBuffer::new(display, &[]).unwrap();
         let indices = glium::index::NoIndices(glium::index::PrimitiveType::TrianglesList);
-        let surface_alpha = 1.0;
-        let shadow_color = Color::custom_white(0.4).to_gl();
+        let lighting = Lighting::default();
         let draw_parameters = glium::DrawParameters {
-            depth: glium::Depth { test: glium::DepthTest::IfLess, write: true, ..Default::default() },
+            depth: glium::Depth { test: glium::DepthTest::IfLessOrEqual, write: false, ..Default::default() },
             blend: glium::Blend::alpha_blending(),
             smooth: Some(glium::Smooth::Nicest),
             ..Default::default()
         };
-        ShadowRenderer { program, vertex_buffer, indices, modelview, surface_alpha, shadow_color, draw_parameters }
+        ShadowRenderer { program, vertex_buffer, layers: Layers::default(), indices, modelview, lighting, draw_parameters }
     }
 
     pub fn set_modelview(&mut self, modelview: [[f32; 4]; 4]) {
         self.modelview = modelview;
     }
 
-    pub fn set_patch(&mut self, patch: &Patch, screen_dimensions: (f32, f32)) {
-        self.vertex_buffer.write(&patch.shadow_trianglelist(screen_dimensions));
-        self.surface_alpha = patch.color.a;
+    pub fn set_lighting(&mut self, lighting: Lighting) {
+        self.lighting = lighting;
     }
 
-    pub fn draw(&self, frame: &mut glium::Frame) {
-        if self.surface_alpha > 0.0 {
-            let uniforms = uniform! { modelview: self.modelview, uniformcolor: self.shadow_color };
-            frame.draw(&self.vertex_buffer, &self.indices, &self.program, &uniforms, &self.draw_parameters).unwrap();
+    // Casters are patches with the radii of their corners, clockwise from the top left.
+    pub fn set_casters<F: Facade>(&mut self, casters: &[(Patch, [f32; 4])], screen_dimensions: (f32, f32), display: &F) {
+        let mut casters = casters.iter().filter(|&&(ref patch, _)| patch.color.a > 0.0).collect::<Vec<_>>();
+        sort_by_approach(&mut casters, |&&(ref patch, _)| patch.shadow_approach());
+        let mut layers = Layers::default();
+        let mut vertices = Vec::new();
+        for &(ref patch, corners) in casters {
+            let shadow_vertices = patch.shadow_trianglelist(corners, screen_dimensions, &self.lighting);
+            layers.push(patch.shadow_approach(), shadow_vertices.len());
+            vertices.extend(shadow_vertices);
         }
+        self.layers = layers;
+        self.vertex_buffer = glium::VertexBuffer::new(display, &vertices).unwrap();
     }
-}
 
-const PANEL_VERTEX_COUNT: usize = 6;
-const PANEL_COUNT: usize = 3;
-const SHADOW_TRIANGLELIST_VERTEX_COUNT: usize = PANEL_COUNT * PANEL_VERTEX_COUNT;
+    pub fn approaches(&self) -> Vec<f32> {
+        self.layers.approaches()
+    }
 
-impl Patch {
-    fn shadow_trianglelist(&self, screen_dim: (f32, f32)) -> Vec<Vertex> {
-        let surface = self.cage.limits();
-        let shadow = shadow_dim(screen_dim, surface);
-        let (lt_bottom, rt_bottom, rb_bottom, lb_bottom) = bottom_panel(surface, shadow);
-        let (lt_right, rt_right, rb_right, lb_right) = right_panel(surface, shadow);
-        let (lt_left, rt_left, rb_left, lb_left) = left_panel(surface, shadow);
-        vec![lt_bottom, rt_bottom, lb_bottom, lb_bottom, rt_bottom, rb_bottom,
-             lt_right, rt_right, lb_right, lb_right, rt_right, rb_right,
-             lt_left, rt_left, lb_left, lb_left, rt_left, rb_left,
-        ]
+    pub fn draw(&self, frame: &mut glium::Frame, approach: f32) {
+        if let Some(range) = self.layers.range(approach) {
+            let uniforms = uniform! { modelview: self.modelview };
+            let vertices = self.vertex_buffer.slice(range).unwrap();
+            frame.draw(vertices, &self.indices, &self.program, &uniforms, &self.draw_parameters).unwrap();
+        }
     }
 }
 
-fn left_panel(surface: (f32, f32, f32, f32, f32, f32), shadow: (f32, f32, f32, f32)) -> (Vertex, Vertex, Vertex, Vertex) {
-    let (surface_left, surface_right, surface_bottom, _surface_top, surface_far, _) = surface;
-    let (shadow_left, shadow_top, _shadow_right, shadow_bottom) = shadow;
+impl Patch {
+    fn shadow_approach(&self) -> f32 {
+        let (_, _, _, _, far, _) = self.cage.limits();
+        (far.max(0.0) - SHADOW_DROP).max(0.0)
+    }
 
-    let panel_top_x = if shadow_left > surface_left || surface_left == surface_right {
-        shadow_left
-    } else {
-        surface_left
-    };
-    let lt_vertex = Vertex { position: [panel_top_x, shadow_top, surface_far] };
-    let rt_vertex = Vertex { position: [panel_top_x, surface_bottom, surface_far] };
-    let rb_vertex = Vertex { position: [shadow_left, shadow_bottom, 0.0] };
-    let lb_vertex = Vertex { position: [shadow_left, shadow_top, 0.0] };
-    (lt_vertex, rt_vertex, rb_vertex, lb_vertex)
+    // Material elevation casts a tight key shadow pushed away from the light and a wider
+    // ambient shadow all around. Both spread with the patch's approach.
+    fn shadow_trianglelist(&self, corners: [f32; 4], screen_dim: (f32, f32), lighting: &Lighting) -> Vec<ShadowVertex> {
+        let (left, right, bottom, top, far, _) = self.cage.limits();
+        let elevation = far.max(0.0);
+        if elevation == 0.0 {
+            return Vec::new();
+        }
+        let caster = (left, top, right, bottom);
+        let clip = self.clip_bounds();
+        let softness = 1.0 + lighting.softness.max(0.0);
+        let z = self.shadow_approach();
+        let fade = (1.0 - elevation / MAX_APPROACH).max(0.25);
+        let center = ((left + right) / 2.0, (top + bottom) / 2.0);
+
+        let ambient_color = shadow_color(lighting, AMBIENT_SHADOW_ALPHA * self.color.a);
+        let ambient = shadow_quad(caster, corners, (0.0, 0.0), elevation * 0.75 * softness, z, ambient_color, clip);
+        let key_color = shadow_color(lighting, KEY_SHADOW_ALPHA * fade * self.color.a);
+        let key_offset = key_offset(screen_dim, lighting, center, elevation);
+        let key = shadow_quad(caster, corners, key_offset, elevation * 0.5 * softness, z, key_color, clip);
+        ambient.into_iter().chain(key.into_iter()).collect()
+    }
 }
 
-fn right_panel(surface: (f32, f32, f32, f32, f32, f32), shadow: (f32, f32, f32, f32)) -> (Vertex, Vertex, Vertex, Vertex) {
-    let (surface_left, surface_right, surface_bottom, _surface_top, surface_far, _) = surface;
-    let (_shadow_left, shadow_top, shadow_right, shadow_bottom) = shadow;
-
-    let panel_top_x = if shadow_right < surface_right || surface_left == surface_right {
-        shadow_right
-    } else {
-        surface_right
-    };
-    let lt_vertex = Vertex { position: [panel_top_x, surface_bottom, surface_far] };
-    let rt_vertex = Vertex { position: [panel_top_x, shadow_top, surface_far] };
-    let rb_vertex = Vertex { position: [shadow_right, shadow_top, 0.0] };
-    let lb_vertex = Vertex { position: [shadow_right, shadow_bottom, 0.0] };
-    (lt_vertex, rt_vertex, rb_vertex, lb_vertex)
+fn shadow_color(lighting: &Lighting, alpha: f32) -> [f32; 4] {
+    let mut color = lighting.shadow_color.to_gl();
+    color[3] *= alpha;
+    color
 }
 
-fn bottom_panel(surface: (f32, f32, f32, f32, f32, f32), shadow: (f32, f32, f32, f32)) -> (Vertex, Vertex, Vertex, Vertex) {
-    let (surface_left, surface_right, surface_bottom, _surface_top, surface_far, _) = surface;
-    let (shadow_left, _shadow_top, shadow_right, shadow_bottom) = shadow;
-
-    let panel_top_left = if shadow_left > surface_left {
-        shadow_left
-    } else {
-        surface_left
-    };
-    let panel_top_right = if shadow_right < surface_right {
-        shadow_right
+fn key_offset((screen_width, screen_height): (f32, f32), lighting: &Lighting, (center_x, center_y): (f32, f32), elevation: f32) -> (f32, f32) {
+    let (dx, dy) = (center_x - lighting.light_x * screen_width, center_y - lighting.light_y * screen_height);
+    let distance = (dx * dx + dy * dy).sqrt();
+    if distance == 0.0 {
+        (0.0, 0.0)
     } else {
-        surface_right
-    };
-    let panel_top_y = if surface_bottom == _surface_top {
-        shadow_bottom
-    } else {
-        surface_bottom
-    };
-    let lt_vertex = Vertex { position: [panel_top_left, panel_top_y, surface_far] };
-    let rt_vertex = Vertex { position: [panel_top_right, panel_top_y, surface_far] };
-    let rb_vertex = Vertex { position: [shadow_right, shadow_bottom, 0.0] };
-    let lb_vertex = Vertex { position: [shadow_left, shadow_bottom, 0.0] };
-    (lt_vertex, rt_vertex, rb_vertex, lb_vertex)
+        let reach = elevation * 0.75 / lighting.light_height.max(0.01);
+        (dx / distance * reach, dy / distance * reach)
+    }
 }
 
-fn shadow_dim((screen_width, screen_height): (f32, f32),
-              (left, right, bottom, top, far, _): (f32, f32, f32, f32, f32, f32)) -> (f32, f32, f32, f32) {
-    let screen_half_height = screen_height / 2.0;
-    let light_x = screen_width / 2.0;
-    let light_y = -0.25 * screen_half_height;
-    let light_z = 3.0 * screen_half_height;
-    let distance_top_from_light = top - light_y;
-    let distance_bottom_from_light = bottom - light_y;
-    let distance_left_from_light = left - light_x;
-    let distance_right_from_light = right - light_x;
-
-    let shadow_factor = light_z / (light_z - far);
-
-    let shadow_top = light_y + distance_top_from_light * shadow_factor;
-    let shadow_bottom = light_y + distance_bottom_from_light * shadow_factor;
-    let shadow_left = light_x + distance_left_from_light * shadow_factor;
-    let shadow_right = light_x + distance_right_from_light * shadow_factor;
-    (shadow_left, shadow_top, shadow_right, shadow_bottom)
+fn shadow_quad((left, top, right, bottom): (f32, f32, f32, f32), corners: [f32; 4], (dx, dy): (f32, f32), sigma: f32, z: f32, color: [f32; 4], clip: [f32; 4]) -> Vec<ShadowVertex> {
+    let sigma = sigma.max(MIN_SHADOW_SIGMA);
+    let caster = [left + dx, top + dy, right + dx, bottom + dy];
+    let reach = 3.0 * sigma;
+    let (quad_left, quad_top, quad_right, quad_bottom) = (caster[0] - reach, caster[1] - reach, caster[2] + reach, caster[3] + reach);
+    let vertex = |x: f32, y: f32| ShadowVertex { position: [x, y, z], color, caster, corners, sigma, clip };
+    vec![
+        vertex(quad_left, quad_top), vertex(quad_right, quad_top), vertex(quad_left, quad_bottom),
+        vertex(quad_left, quad_bottom), vertex(quad_right, quad_top), vertex(quad_right, quad_bottom),
+    ]
 }
@@ -0,0 +1,84 @@
+use glium;
+use glium::backend::Facade;
+use glium::Surface as GliumSurface;
+use super::layers::{sort_by_approach, Layers};
+use super::model::{Surface, SurfaceVertex};
+
+// Draws rounded and bordered surfaces with anti-aliased edges. Every surface blends, so
+// they are drawn one approach at a time in the screen's back-to-front pass.
+pub struct SurfaceRenderer {
+    pub program: glium::Program,
+    pub vertex_buffer: glium::VertexBuffer<SurfaceVertex>,
+    layers: Layers,
+    pub indices: glium::index::NoIndices,
+    modelview: [[f32; 4]; 4],
+    draw_parameters: glium::DrawParameters<'static>,
+}
+
+impl SurfaceRenderer {
+    pub fn new<F: Facade>(display: &F, modelview: [[f32; 4]; 4]) -> Self {
+        let vertex_shader_src = include_str!("shaders/surface_vertex_shader.glsl");
+        let fragment_shader_src = include_str!("shaders/surface_fragment_shader.glsl");
+        let program = glium::Program::from_source(display, vertex_shader_src, fragment_shader_src, None).unwrap();
+        let vertex_buffer = glium::VertexBuffer::new(display, &[]).unwrap();
+        let indices = glium::index::NoIndices(glium::index::PrimitiveType::TrianglesList);
+        let draw_parameters = glium::DrawParameters {
+            depth: glium::Depth { test: glium::DepthTest::IfLessOrEqual, write: false, ..Default::default() },
+            blend: glium::Blend::alpha_blending(),
+            smooth: Some(glium::Smooth::Nicest),
+            ..Default::default()
+        };
+        SurfaceRenderer { program, vertex_buffer, layers: Layers::default(), indices, modelview, draw_parameters }
+    }
+
+    pub fn set_modelview(&mut self, modelview: [[f32; 4]; 4]) {
+        self.modelview = modelview;
+    }
+
+    pub fn set_surfaces<F: Facade>(&mut self, surfaces: &[Surface], display: &F) {
+        let mut surfaces = surfaces.iter()
+            .filter(|surface| surface.patch.color.a > 0.0 || (surface.border_width > 0.0 && surface.border_color.a > 0.0))
+            .collect::<Vec<_>>();
+        sort_by_approach(&mut surfaces, |surface| surface.approach());
+        let mut layers = Layers::default();
+        let mut vertices = Vec::new();
+        for surface in surfaces {
+            let surface_vertices = surface.trianglelist();
+            layers.push(surface.approach(), surface_vertices.len());
+            vertices.extend(surface_vertices);
+        }
+        self.layers = layers;
+        self.vertex_buffer = glium::VertexBuffer::new(display, &vertices).unwrap();
+    }
+
+    pub fn approaches(&self) -> Vec<f32> {
+        self.layers.approaches()
+    }
+
+    pub fn draw(&self, frame: &mut glium::Frame, approach: f32) {
+        if let Some(range) = self.layers.range(approach) {
+            let uniforms = uniform! { modelview: self.modelview };
+            let vertices = self.vertex_buffer.slice(range).unwrap();
+            frame.draw(vertices, &self.indices, &self.program, &uniforms, &self.draw_parameters).unwrap();
+        }
+    }
+}
+
+impl Surface {
+    fn approach(&self) -> f32 {
+        let (_, _, _, _, far, _) = self.patch.cage.limits();
+        far
+    }
+
+    fn trianglelist(&self) -> Vec<SurfaceVertex> {
+        let (left, right, bottom, top, far, _) = self.patch.cage.limits();
+        let (color, border_color) = (self.patch.color.to_gl(), self.border_color.to_gl());
+        let (rect, corners, border_width) = ([left, top, right, bottom], self.corners, self.border_width);
+        let clip = self.patch.clip_bounds();
+        let vertex = |x: f32, y: f32| SurfaceVertex { position: [x, y, far], color, border_color, rect, corners, border_width, clip };
+        vec![
+            vertex(left, top), vertex(right, top), vertex(left, bottom),
+            vertex(left, bottom), vertex(right, top), vertex(right, bottom),
+        ]
+    }
+}
@@ -0,0 +1,260 @@
+use flood::Path;
+use std::f32::consts::PI;
+use std::mem;
+
+const JOIN_SEGMENTS: usize = 12;
+
+// Clips a convex polygon to an axis-aligned rectangle given as left, top, right, bottom.
+pub fn clip_polygon(points: Vec<(f32, f32)>, (left, top, right, bottom): (f32, f32, f32, f32)) -> Vec<(f32, f32)> {
+    let points = clip_edge(points, |(x, _)| x - left, |a, b| lerp_at_x(a, b, left));
+    let points = clip_edge(points, |(x, _)| right - x, |a, b| lerp_at_x(a, b, right));
+    let points = clip_edge(points, |(_, y)| y - top, |a, b| lerp_at_y(a, b, top));
+    clip_edge(points, |(_, y)| bottom - y, |a, b| lerp_at_y(a, b, bottom))
+}
+
+// Splits a convex polygon into triangles sharing its first point.
+pub fn fan<T: Copy>(points: &[T]) -> Vec<T> {
+    if points.len() < 3 {
+        return Vec::new();
+    }
+    (1..points.len() - 1)
+        .flat_map(|i| vec![points[0], points[i], points[i + 1]])
+        .collect()
+}
+
+fn clip_edge<F, G>(points: Vec<(f32, f32)>, inside: F, crossing: G) -> Vec<(f32, f32)> where
+    F: Fn((f32, f32)) -> f32,
+    G: Fn((f32, f32), (f32, f32)) -> (f32, f32),
+{
+    let mut clipped = Vec::new();
+    for (i, &point) in points.iter().enumerate() {
+        let previous = points[(i + points.len() - 1) % points.len()];
+        let (point_inside, previous_inside) = (inside(point) >= 0.0, inside(previous) >= 0.0);
+        if point_inside != previous_inside {
+            push_distinct(&mut clipped, crossing(previous, point));
+        }
+        if point_inside {
+            push_distinct(&mut clipped, point);
+        }
+    }
+    if clipped.len() > 1 && clipped.first() == clipped.last() {
+        clipped.pop();
+    }
+    clipped
+}
+
+// Points lying on a clip edge would otherwise come back twice.
+fn push_distinct(points: &mut Vec<(f32, f32)>, point: (f32, f32)) {
+    if points.last() != Some(&point) {
+        points.push(point);
+    }
+}
+
+fn lerp_at_x((ax, ay): (f32, f32), (bx, by): (f32, f32), x: f32) -> (f32, f32) {
+    (x, ay + (by - ay) * (x - ax) / (bx - ax))
+}
+
+fn lerp_at_y((ax, ay): (f32, f32), (bx, by): (f32, f32), y: f32) -> (f32, f32) {
+    (ax + (bx - ax) * (y - ay) / (by - ay), y)
+}
+
+// Turns path commands into subpaths of points, each flagged when it was closed.
+pub fn flatten(paths: &[Path]) -> Vec<(Vec<(f32, f32)>, bool)> {
+    let mut subpaths = Vec::new();
+    let mut current = Vec::new();
+    for path in paths {
+        match path {
+            &Path::Move(x, y) => {
+                finish_subpath(&mut subpaths, &mut current, false);
+                current.push((x, y));
+            }
+            &Path::Line(x, y) => current.push((x, y)),
+            &Path::Arc(center, radius, start, sweep) => current.extend(arc_points(center, radius, start, sweep)),
+            &Path::Circle(center, radius) => {
+                finish_subpath(&mut subpaths, &mut current, false);
+                let mut circle = arc_points(center, radius, 0.0, 360.0);
+                circle.pop();
+                subpaths.push((circle, true));
+            }
+            &Path::Close => finish_subpath(&mut subpaths, &mut current, true),
+        }
+    }
+    finish_subpath(&mut subpaths, &mut current, false);
+    subpaths
+}
+
+fn finish_subpath(subpaths: &mut Vec<(Vec<(f32, f32)>, bool)>, current: &mut Vec<(f32, f32)>, closed: bool) {
+    let points = mem::replace(current, Vec::new());
+    if points.len() > 1 {
+        subpaths.push((points, closed));
+    }
+}
+
+// Segments stay about two pixels long along the arc.
+fn arc_points((center_x, center_y): (f32, f32), radius: f32, start: f32, sweep: f32) -> Vec<(f32, f32)> {
+    let segments = (sweep.abs().to_radians() * radius / 2.0).ceil().max(4.0).min(256.0) as usize;
+    (0..segments + 1)
+        .map(|i| (start + sweep * i as f32 / segments as f32).to_radians())
+        .map(|angle| (center_x + radius * angle.cos(), center_y + radius * angle.sin()))
+        .collect()
+}
+
+// Ear-clips a simple polygon. Whatever remains of a self-intersecting polygon is fanned.
+pub fn fill_triangles(polygon: &[(f32, f32)]) -> Vec<(f32, f32)> {
+    let area = signed_area(polygon);
+    if polygon.len() < 3 || area == 0.0 {
+        return Vec::new();
+    }
+    let mut indices = (0..polygon.len()).collect::<Vec<_>>();
+    let mut triangles = Vec::new();
+    while indices.len() > 3 {
+        let count = indices.len();
+        let ear = (0..count).find(|&i| is_ear(polygon, &indices, i, area));
+        match ear {
+            Some(i) => {
+                let (a, b, c) = (indices[(i + count - 1) % count], indices[i], indices[(i + 1) % count]);
+                triangles.extend(vec![polygon[a], polygon[b], polygon[c]]);
+                indices.remove(i);
+            }
+            None => break,
+        }
+    }
+    let remainder = indices.iter().map(|&i| polygon[i]).collect::<Vec<_>>();
+    triangles.extend(fan(&remainder));
+    triangles
+}
+
+fn is_ear(polygon: &[(f32, f32)], indices: &[usize], i: usize, area: f32) -> bool {
+    let count = indices.len();
+    let (a, b, c) = (polygon[indices[(i + count - 1) % count]], polygon[indices[i]], polygon[indices[(i + 1) % count]]);
+    if cross(a, b, c) * area <= 0.0 {
+        return false;
+    }
+    !indices.iter()
+        .map(|&index| polygon[index])
+        .filter(|&point| point != a && point != b && point != c)
+        .any(|point| cross(a, b, point) * area >= 0.0 && cross(b, c, point) * area >= 0.0 && cross(c, a, point) * area >= 0.0)
+}
+
+fn cross((ax, ay): (f32, f32), (bx, by): (f32, f32), (cx, cy): (f32, f32)) -> f32 {
+    (bx - ax) * (cy - by) - (by - ay) * (cx - bx)
+}
+
+fn signed_area(polygon: &[(f32, f32)]) -> f32 {
+    (0..polygon.len())
+        .map(|i| {
+            let ((ax, ay), (bx, by)) = (polygon[i], polygon[(i + 1) % polygon.len()]);
+            ax * by - bx * ay
+        })
+        .sum::<f32>() / 2.0
+}
+
+// Covers each segment with a quad and fills the outside of each turn with a round wedge.
+// Quads meet at the inner corner of a turn so a translucent stroke covers every point
+// once. Turns too sharp for that corner to fit within both segments keep full quads.
+pub fn stroke_triangles(points: &[(f32, f32)], closed: bool, width: f32) -> Vec<(f32, f32)> {
+    let mut points = points.to_vec();
+    points.dedup();
+    if closed && points.len() > 2 && points.first() == points.last() {
+        points.pop();
+    }
+    if points.len() < 2 || width <= 0.0 {
+        return Vec::new();
+    }
+    let half = width / 2.0;
+    let count = points.len();
+    let segments = if closed { count } else { count - 1 };
+    let ends = |i: usize| (points[i], points[(i + 1) % count]);
+    let length = |i: usize| {
+        let ((ax, ay), (bx, by)) = ends(i);
+        ((bx - ax).powi(2) + (by - ay).powi(2)).sqrt()
+    };
+    let normals = (0..segments)
+        .map(|i| {
+            let ((ax, ay), (bx, by)) = ends(i);
+            (-(by - ay) / length(i) * half, (bx - ax) / length(i) * half)
+        })
+        .collect::<Vec<_>>();
+    // Corners on the normal side and the opposite side at the start and end of each segment.
+    let mut corners = (0..segments)
+        .map(|i| {
+            let (((ax, ay), (bx, by)), (nx, ny)) = (ends(i), normals[i]);
+            [[(ax + nx, ay + ny), (ax - nx, ay - ny)], [(bx + nx, by + ny), (bx - nx, by - ny)]]
+        })
+        .collect::<Vec<_>>();
+
+    let mut triangles = Vec::new();
+    let joints = if closed { 0..segments } else { 1..segments };
+    for joint in joints {
+        let (before, after) = ((joint + segments - 1) % segments, joint);
+        let ((x, y), (n1x, n1y), (n2x, n2y)) = (points[joint], normals[before], normals[after]);
+        let turn = n1y * -n2x - -n1x * n2y;
+        let cos = (n1x * n2x + n1y * n2y) / (half * half);
+        if turn == 0.0 && cos > 0.0 {
+            continue;
+        }
+        let outer = if turn > 0.0 { -1.0 } else { 1.0 };
+        let mut pivot = (x, y);
+        if cos > -0.99 {
+            let (mx, my) = ((n1x + n2x) / (1.0 + cos), (n1y + n2y) / (1.0 + cos));
+            let reach = (mx * mx + my * my - half * half).max(0.0).sqrt();
+            if reach <= length(before) / 2.0 && reach <= length(after) / 2.0 {
+                let inner = (x - outer * mx, y - outer * my);
+                let side = if outer > 0.0 { 1 } else { 0 };
+                corners[before][1][side] = inner;
+                corners[after][0][side] = inner;
+                pivot = inner;
+            }
+        }
+        let start = (outer * n1y).atan2(outer * n1x);
+        let mut sweep = (outer * n2y).atan2(outer * n2x) - start;
+        if sweep > PI {
+            sweep -= 2.0 * PI;
+        } else if sweep <= -PI {
+            sweep += 2.0 * PI;
+        }
+        let steps = ((sweep.abs() / (2.0 * PI) * JOIN_SEGMENTS as f32).ceil() as usize).max(1);
+        let mut wedge = vec![pivot];
+        wedge.extend((0..steps + 1)
+            .map(|step| start + sweep * step as f32 / steps as f32)
+            .map(|angle| (x + half * angle.cos(), y + half * angle.sin())));
+        triangles.extend(fan(&wedge));
+    }
+    for &[[start_left, start_right], [end_left, end_right]] in &corners {
+        triangles.extend(fan(&[start_left, end_left, end_right, start_right]));
+    }
+    triangles
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn clip_polygon_trims_square_to_rect() {
+        let square = vec![(-5.0, -5.0), (15.0, -5.0), (15.0, 15.0), (-5.0, 15.0)];
+        let clipped = clip_polygon(square, (0.0, 0.0, 10.0, 10.0));
+        assert_eq!(4, clipped.len());
+        for corner in &[(0.0, 0.0), (10.0, 0.0), (10.0, 10.0), (0.0, 10.0)] {
+            assert!(clipped.contains(corner));
+        }
+    }
+
+    #[test]
+    fn fill_triangles_clips_concave_polygon() {
+        let chevron = [(0.0, 0.0), (10.0, 5.0), (0.0, 10.0), (4.0, 5.0)];
+        assert_eq!(6, fill_triangles(&chevron).len());
+    }
+
+    #[test]
+    fn stroke_triangles_do_not_overlap_at_a_turn() {
+        let triangles = stroke_triangles(&[(0.0, 0.0), (10.0, 0.0), (10.0, 10.0)], false, 2.0);
+        let area = triangles.chunks(3).map(|triangle| cross(triangle[0], triangle[1], triangle[2]).abs() / 2.0).sum::<f32>();
+        assert!((area - (39.0 + PI / 4.0)).abs() < 0.1);
+    }
+
+    #[test]
+    fn fan_splits_quad_into_two_triangles() {
+        assert_eq!(vec![0, 1, 2, 0, 2, 3], fan(&[0, 1, 2, 3]));
+    }
+}
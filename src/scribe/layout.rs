@@ -2,13 +2,15 @@ use unicode_normalization::UnicodeNormalization;
 use rusttype::{Font, PositionedGlyph, Scale};
 use super::glyph_writer::GlyphWriter;
 
-pub fn fit_text<'a>(font: &'a Font, text: &str, mut scale: Scale, width: i32, placement: f32) -> Vec<PositionedGlyph<'a>>
+// Keeps the text's lines when they all fit within the height and otherwise narrows the
+// glyphs until the text fits on a single line.
+pub fn fit_text<'a>(font: &'a Font, text: &str, mut scale: Scale, width: i32, height: i32, placement: f32) -> Vec<PositionedGlyph<'a>>
 {
     let mut some_glyphs: Option<Vec<PositionedGlyph>> = None;
     while let None = some_glyphs {
         let lines = break_text(font, text, scale, width, placement);
         let line_count = lines.len();
-        if line_count <= 1 || scale.x <= 1.0 {
+        if line_count <= 1 || line_count as f32 * line_stride(font, scale) <= height as f32 || scale.x <= 1.0 {
             let glyphs = lines.into_iter().fold(Vec::new(), |mut glyphs, (_, more)| {
                 glyphs.extend(more);
                 glyphs
@@ -21,6 +23,11 @@ pub fn fit_text<'a>(font: &'a Font, text: &str, mut scale: Scale, width: i32, pl
     some_glyphs.expect("glyphs")
 }
 
+pub fn line_stride(font: &Font, scale: Scale) -> f32 {
+    let v_metrics = font.v_metrics(scale);
+    v_metrics.ascent - v_metrics.descent + v_metrics.line_gap
+}
+
 pub fn break_text<'a>(font: &'a Font, text: &str, scale: Scale, max_width: i32, placement: f32) -> Vec<(f32, Vec<PositionedGlyph<'a>>)> {
     let mut glyph_writer = GlyphWriter::new(&font.v_metrics(scale));
     let mut last_glyph_id = None;
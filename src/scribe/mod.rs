@@ -1,20 +1,127 @@
-use rusttype::{Font, FontCollection};
+use ::flood::Direction;
+use rusttype::{Font, FontCollection, GlyphId, Point};
 use rusttype::PositionedGlyph;
 pub use rusttype::Scale;
+use std::cell::{Cell, RefCell};
+use std::collections::HashMap;
 
 mod glyph_writer;
 mod layout;
 
+const DEFAULT_EM: f32 = 16.0;
+
+// Besides laying out text, the scribe carries the metrics that lengths resolve against:
+// the screen's dpi factor, the window size and the em of the subtree being laid out.
+// Text sizes are remembered until the next window layout. The scribe also tracks the
+// direction of the subtree being laid out.
 pub struct Scribe<'a> {
     font: Font<'a>,
+    dpi_factor: f32,
+    window: (f32, f32),
+    em: Cell<f32>,
+    direction: Cell<Direction>,
+    text_sizes: RefCell<HashMap<String, f32>>,
+}
+
+#[derive(Copy, Clone, Debug)]
+pub struct GlyphSpot {
+    pub id: GlyphId,
+    pub scale: Scale,
+    pub position: Point<f32>,
 }
 
 impl<'a> Scribe<'a> {
-    pub fn fit_text(&'a self, text: &str, scale: Scale, width: i32, placement: f32) -> Vec<PositionedGlyph<'a>> {
-        layout::fit_text(&self.font, text, scale, width, placement)
+    pub fn fit_text(&'a self, text: &str, scale: Scale, width: i32, height: i32, placement: f32) -> Vec<PositionedGlyph<'a>> {
+        layout::fit_text(&self.font, text, scale, width, height, placement)
+    }
+
+    pub fn fit_spots(&self, text: &str, scale: Scale, width: i32, height: i32, placement: f32) -> Vec<GlyphSpot> {
+        layout::fit_text(&self.font, text, scale, width, height, placement).iter()
+            .map(|glyph| GlyphSpot { id: glyph.id(), scale: glyph.scale(), position: glyph.position() })
+            .collect()
+    }
+
+    pub fn position_spots(&'a self, spots: &[GlyphSpot]) -> Vec<PositionedGlyph<'a>> {
+        spots.iter()
+            .filter_map(|spot| self.font.glyph(spot.id).map(|glyph| glyph.scaled(spot.scale).positioned(spot.position)))
+            .collect()
+    }
+
+    pub fn font(&self) -> &Font<'a> {
+        &self.font
+    }
+
+    pub fn dpi_factor(&self) -> f32 {
+        self.dpi_factor
+    }
+
+    pub fn set_dpi_factor(&mut self, dpi_factor: f32) {
+        self.dpi_factor = dpi_factor;
+    }
+
+    pub fn window(&self) -> (f32, f32) {
+        self.window
+    }
+
+    // Starts a layout of the window, with the em back at its default.
+    pub fn set_window(&mut self, width: f32, height: f32) {
+        self.window = (width, height);
+        self.em.set(DEFAULT_EM);
+        self.text_sizes.borrow_mut().clear();
+    }
+
+    pub fn em(&self) -> f32 {
+        self.em.get()
+    }
+
+    pub fn line_height(&self) -> f32 {
+        layout::line_stride(&self.font, Scale::uniform(self.em()))
+    }
+
+    // Lines the text takes when set at the em and wrapped within the width.
+    pub fn count_lines(&self, text: &str, width: f32) -> usize {
+        if width <= 0.0 {
+            return 1;
+        }
+        layout::break_text(&self.font, text, Scale::uniform(self.em()), width as i32, 0.0).len().max(1)
+    }
+
+    pub fn with_em<T, F>(&self, em: f32, f: F) -> T where
+        F: FnOnce() -> T
+    {
+        let enclosing_em = self.em.replace(em);
+        let result = f();
+        self.em.set(enclosing_em);
+        result
+    }
+
+    pub fn direction(&self) -> Direction {
+        self.direction.get()
+    }
+
+    pub fn set_direction(&mut self, direction: Direction) {
+        self.direction.set(direction);
+    }
+
+    pub fn with_direction<T, F>(&self, direction: Direction, f: F) -> T where
+        F: FnOnce() -> T
+    {
+        let enclosing_direction = self.direction.replace(direction);
+        let result = f();
+        self.direction.set(enclosing_direction);
+        result
     }
 
     pub fn size_text(&self, text: &str) -> f32 {
+        if let Some(&size) = self.text_sizes.borrow().get(text) {
+            return size;
+        }
+        let size = self.measure_text(text);
+        self.text_sizes.borrow_mut().insert(text.to_owned(), size);
+        size
+    }
+
+    fn measure_text(&self, text: &str) -> f32 {
         let lines = layout::break_text(&self.font, text, Scale::uniform(1.0), 60000i32, 0.0);
         if lines.is_empty() {
             0.0
@@ -28,6 +135,6 @@ impl<'a> Scribe<'a> {
 impl<'a> Default for Scribe<'a> {
     fn default() -> Self {
         let font = FontCollection::from_bytes(include_bytes!("Arial Unicode.ttf") as &[u8]).into_font().unwrap();
-        Scribe { font }
+        Scribe { font, dpi_factor: 1.0, window: (0.0, 0.0), em: Cell::new(DEFAULT_EM), direction: Cell::new(Direction::default()), text_sizes: RefCell::new(HashMap::new()) }
     }
 }
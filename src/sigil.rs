@@ -1,9 +1,14 @@
-use super::{Color, WebColor};
+use ::flood::{Fit, Gradient, Ink, Path};
+use super::{Color, ImageSource, WebColor};
 
-#[derive(Clone, Debug)]
+#[derive(Clone, PartialEq, Debug)]
 pub enum Sigil {
     Color(Color),
+    Gradient(Gradient),
+    Surface { color: Color, corners: [f32; 4], border_width: f32, border_color: Color },
     Paragraph { line_height: f32, text: String, color: Color, placement: f32 },
+    Image { source: ImageSource, fit: Fit, tint: Color },
+    Canvas { paths: Vec<Path>, inks: Vec<Ink> },
     Touch(u64),
 }
 
@@ -12,3 +17,89 @@ impl Default for Sigil {
         Sigil::Color(Color::from(WebColor::DeepPink))
     }
 }
+
+impl Sigil {
+    // Corners run clockwise from the top left. Only color and surface sigils take a shape.
+    pub fn with_corners(&self, corners: [f32; 4]) -> Self {
+        match self {
+            &Sigil::Color(color) => Sigil::Surface { color, corners, border_width: 0.0, border_color: Color { a: 0.0, ..color } },
+            &Sigil::Surface { color, border_width, border_color, .. } => Sigil::Surface { color, corners, border_width, border_color },
+            sigil => sigil.clone(),
+        }
+    }
+
+    pub fn with_border(&self, border_width: f32, border_color: Color) -> Self {
+        match self {
+            &Sigil::Color(color) => Sigil::Surface { color, corners: [0.0; 4], border_width, border_color },
+            &Sigil::Surface { color, corners, .. } => Sigil::Surface { color, corners, border_width, border_color },
+            sigil => sigil.clone(),
+        }
+    }
+
+    // Flips text placement, corner radii, gradients and canvas paths for a block of the given
+    // width mirrored across its parent. Images are not flipped.
+    pub fn mirrored(&self, width: f32) -> Self {
+        match self {
+            &Sigil::Gradient(Gradient::Linear(angle, ref stops)) => Sigil::Gradient(Gradient::Linear(360.0 - angle, stops.clone())),
+            &Sigil::Gradient(Gradient::Radial((x, y), radius, ref stops)) => {
+                Sigil::Gradient(Gradient::Radial((1.0 - x, y), radius, stops.clone()))
+            }
+            &Sigil::Canvas { ref paths, ref inks } => {
+                let paths = paths.iter()
+                    .map(|path| match path {
+                        &Path::Move(x, y) => Path::Move(width - x, y),
+                        &Path::Line(x, y) => Path::Line(width - x, y),
+                        &Path::Arc((x, y), radius, start, sweep) => Path::Arc((width - x, y), radius, 180.0 - start, -sweep),
+                        &Path::Circle((x, y), radius) => Path::Circle((width - x, y), radius),
+                        &Path::Close => Path::Close,
+                    })
+                    .collect();
+                Sigil::Canvas { paths, inks: inks.clone() }
+            }
+            &Sigil::Surface { color, corners: [top_left, top_right, bottom_right, bottom_left], border_width, border_color } => {
+                Sigil::Surface { color, corners: [top_right, top_left, bottom_left, bottom_right], border_width, border_color }
+            }
+            &Sigil::Paragraph { line_height, ref text, color, placement } => {
+                Sigil::Paragraph { line_height, text: text.to_owned(), color, placement: 1.0 - placement }
+            }
+            sigil => sigil.clone(),
+        }
+    }
+
+    // Multiplies alpha, clamped to 0..1, into every color the sigil draws with.
+    pub fn with_opacity(&self, alpha: f32) -> Self {
+        let alpha = alpha.max(0.0).min(1.0);
+        match self {
+            &Sigil::Color(color) => Sigil::Color(fade(color, alpha)),
+            &Sigil::Gradient(Gradient::Linear(angle, ref stops)) => Sigil::Gradient(Gradient::Linear(angle, fade_stops(stops, alpha))),
+            &Sigil::Gradient(Gradient::Radial(center, radius, ref stops)) => {
+                Sigil::Gradient(Gradient::Radial(center, radius, fade_stops(stops, alpha)))
+            }
+            &Sigil::Surface { color, corners, border_width, border_color } => {
+                Sigil::Surface { color: fade(color, alpha), corners, border_width, border_color: fade(border_color, alpha) }
+            }
+            &Sigil::Paragraph { line_height, ref text, color, placement } => {
+                Sigil::Paragraph { line_height, text: text.to_owned(), color: fade(color, alpha), placement }
+            }
+            &Sigil::Image { ref source, fit, tint } => Sigil::Image { source: source.clone(), fit, tint: fade(tint, alpha) },
+            &Sigil::Canvas { ref paths, ref inks } => {
+                let inks = inks.iter()
+                    .map(|ink| match ink {
+                        &Ink::Fill(color) => Ink::Fill(fade(color, alpha)),
+                        &Ink::Stroke(width, color) => Ink::Stroke(width, fade(color, alpha)),
+                    })
+                    .collect();
+                Sigil::Canvas { paths: paths.clone(), inks }
+            }
+            &Sigil::Touch(tag) => Sigil::Touch(tag),
+        }
+    }
+}
+
+fn fade(color: Color, alpha: f32) -> Color {
+    Color { a: color.a * alpha, ..color }
+}
+
+fn fade_stops(stops: &[(f32, Color)], alpha: f32) -> Vec<(f32, Color)> {
+    stops.iter().map(|&(stop, color)| (stop, fade(color, alpha))).collect()
+}
@@ -0,0 +1,73 @@
+use flood::Flood;
+use std::collections::HashMap;
+use traits::{Component, Update};
+
+// Models of stateful widgets keyed by id. Missing models read as their default.
+#[derive(Clone, PartialEq, Debug)]
+pub struct Store<MdlT> {
+    mdls: HashMap<u64, MdlT>,
+}
+
+impl<MdlT> Default for Store<MdlT> {
+    fn default() -> Self {
+        Store { mdls: HashMap::new() }
+    }
+}
+
+impl<MdlT> Store<MdlT> where
+    MdlT: Default + Clone
+{
+    pub fn get(&self, id: u64) -> MdlT {
+        self.mdls.get(&id).cloned().unwrap_or_default()
+    }
+
+    pub fn set(&mut self, id: u64, mdl: MdlT) {
+        self.mdls.insert(id, mdl);
+    }
+
+    pub fn update<MsgT>(&mut self, id: u64, msg: MsgT) where
+        MdlT: Update<MsgT>
+    {
+        self.mdls.entry(id).or_insert_with(MdlT::default).update(msg);
+    }
+
+    // Updates the model through the component, which may depend on what the parent decided.
+    pub fn update_component<C>(&mut self, component: &C, id: u64, msg: C::Msg) where
+        C: Component<Mdl=MdlT>,
+    {
+        component.update(self.mdls.entry(id).or_insert_with(MdlT::default), msg);
+    }
+
+    // Views the component with its model and wraps its messages and events with its id.
+    pub fn view<C, MsgT, F, G>(&self, component: &C, id: u64, msg_wrap: F, evt_wrap: G) -> Flood<MsgT> where
+        C: Component<Mdl=MdlT>,
+        MsgT: Clone + Send + Sync + 'static,
+        F: Fn(u64, C::Msg) -> MsgT + Send + Sync + 'static,
+        G: Fn(u64, C::Evt) -> MsgT + Send + Sync + 'static,
+    {
+        component.view(id, &self.get(id), move |msg| msg_wrap(id, msg), move |evt| evt_wrap(id, evt))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Copy, Clone, PartialEq, Debug, Default)]
+    struct Tally(u32);
+
+    impl Update<u32> for Tally {
+        fn update(&mut self, msg: u32) {
+            self.0 += msg;
+        }
+    }
+
+    #[test]
+    fn update_starts_from_default_per_id() {
+        let mut store = Store::<Tally>::default();
+        store.update(1, 2);
+        store.update(1, 3);
+        store.update(2, 4);
+        assert_eq!((Tally(5), Tally(4), Tally(0)), (store.get(1), store.get(2), store.get(3)));
+    }
+}
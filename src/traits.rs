@@ -8,3 +8,24 @@ pub trait Draw<MsgT> where MsgT: Clone
 {
     fn draw(&self) -> Flood<MsgT>;
 }
+
+// A stateful widget. The component value holds what the parent decides, like labels and
+// palettes, while the model holds what changes with the component's own messages. Parents
+// keep models in a Store. Views wrap the component's messages, which come back to its
+// update, and its events, which tell the parent what happened, into the parent's messages.
+pub trait Component {
+    type Mdl: Update<Self::Msg> + Default + Clone;
+    type Msg: Clone + Send + Sync + 'static;
+    type Evt: Clone + Send + Sync + 'static;
+
+    fn view<MsgT, F, G>(&self, id: u64, mdl: &Self::Mdl, msg_wrap: F, evt_wrap: G) -> Flood<MsgT> where
+        MsgT: Clone + Send + Sync + 'static,
+        F: Fn(Self::Msg) -> MsgT + Send + Sync + 'static,
+        G: Fn(Self::Evt) -> MsgT + Send + Sync + 'static;
+
+    // Applies one of the component's messages to its model. Most models need nothing from
+    // the component and update themselves.
+    fn update(&self, mdl: &mut Self::Mdl, msg: Self::Msg) {
+        mdl.update(msg);
+    }
+}
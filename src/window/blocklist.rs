@@ -1,5 +1,7 @@
 use ::{Block, TouchMsg};
-use ::flood::{Signal, Timeout, Version, Input};
+use ::base::Rectangle;
+use ::flood::{Direction, Flood, Input, Popup, Raft, Signal, Timeout, Version};
+use super::BlockRange;
 use std::sync::Arc;
 
 pub struct Blocklist<MsgT> where
@@ -9,9 +11,11 @@ pub struct Blocklist<MsgT> where
     pub blocks: Vec<Block>,
     pub touch_adapters: Vec<(u64, Arc<Fn(TouchMsg) -> MsgT + Send + Sync>)>,
     pub input_adapters: Vec<Arc<Fn(Input) -> MsgT + Send + Sync>>,
-    pub raft_msgs: Vec<MsgT>,
+    pub rafts: Vec<(Raft<MsgT>, BlockRange)>,
     pub signals: Vec<Signal<MsgT>>,
     pub timeouts: Vec<Version<Timeout<MsgT>>>,
+    // Popups with their anchoring range and the em and direction in effect where they were raised.
+    pub popups: Vec<(BlockRange, Popup, Flood<MsgT>, f32, Direction)>,
 }
 
 impl<MsgT> Default for Blocklist<MsgT> where
@@ -23,9 +27,10 @@ impl<MsgT> Default for Blocklist<MsgT> where
             blocks: Vec::new(),
             touch_adapters: Vec::new(),
             input_adapters: Vec::new(),
-            raft_msgs: Vec::new(),
+            rafts: Vec::new(),
             signals: Vec::new(),
             timeouts: Vec::new(),
+            popups: Vec::new(),
         }
     }
 }
@@ -41,14 +46,57 @@ impl<MsgT> Blocklist<MsgT> where
         self.max_approach = self.max_approach.max(max_approach)
     }
 
+    // Mirrors blocks, escape ranges and popup anchors from left to right across the range.
+    pub fn mirror(&mut self, range: &BlockRange) {
+        let mirror_x = |x: f32| 2.0 * range.left + range.width - x;
+        for block in self.blocks.iter_mut() {
+            block.anchor.x = mirror_x(block.anchor.x + block.width);
+            block.clip = block.clip.map(|clip| Rectangle { left: mirror_x(clip.right), right: mirror_x(clip.left), ..clip });
+            block.sigil = block.sigil.mirrored(block.width);
+        }
+        for &mut (_, ref mut raft_range) in self.rafts.iter_mut() {
+            raft_range.left = mirror_x(raft_range.left + raft_range.width);
+        }
+        for &mut (ref mut anchor, ref mut popup, _, _, _) in self.popups.iter_mut() {
+            anchor.left = mirror_x(anchor.left + anchor.width);
+            *popup = popup.mirrored();
+        }
+    }
+
+    // Messages from escaped floods about the ranges they were given.
+    pub fn raft_msgs(&self) -> Vec<MsgT> {
+        self.rafts.iter()
+            .map(|&(Raft::RangeAdapter(tag, ref adapter), ref range)| adapter(tag, range))
+            .collect()
+    }
+
     pub fn append(mut self, rhs: &mut Blocklist<MsgT>) -> Self {
         self.max_approach = self.max_approach.max(rhs.max_approach);
         self.blocks.append(&mut rhs.blocks);
         self.touch_adapters.append(&mut rhs.touch_adapters);
         self.input_adapters.append(&mut rhs.input_adapters);
-        self.raft_msgs.append(&mut rhs.raft_msgs);
+        self.rafts.append(&mut rhs.rafts);
         self.signals.append(&mut rhs.signals);
         self.timeouts.append(&mut rhs.timeouts);
+        self.popups.append(&mut rhs.popups);
         self
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use ::{Anchor, Color, Sigil};
+    use super::*;
+
+    #[test]
+    fn mirror_flips_blocks_and_text_placement_across_range() {
+        let range = BlockRange { left: 10.0, top: 0.0, width: 100.0, height: 50.0, approach: 0.0 };
+        let paragraph = Sigil::Paragraph { line_height: 20.0, text: "Text".into(), color: Color::black(), placement: 0.0 };
+        let block = Block { sigil: paragraph, width: 30.0, height: 20.0, anchor: Anchor { x: 20.0, y: 0.0 }, ..Default::default() };
+        let mut blocklist = Blocklist::<()> { blocks: vec![block], ..Default::default() };
+        blocklist.mirror(&range);
+        let block = &blocklist.blocks[0];
+        assert_eq!(70.0, block.anchor.x);
+        assert_eq!(Sigil::Paragraph { line_height: 20.0, text: "Text".into(), color: Color::black(), placement: 1.0 }, block.sigil);
+    }
+}
@@ -18,6 +18,13 @@ impl BlockRange {
         self.with_approach(self.approach + more)
     }
 
+    pub fn with_offset(&self, h_offset: f32, v_offset: f32) -> Self {
+        let mut range = self.clone();
+        range.left += h_offset;
+        range.top += v_offset;
+        range
+    }
+
     pub fn with_padding(&self, h_pad: f32, v_pad: f32) -> Self {
         BlockRange {
             left: self.left + h_pad,
@@ -27,6 +34,16 @@ impl BlockRange {
             approach: self.approach,
         }
     }
+
+    pub fn with_insets(&self, left: f32, top: f32, right: f32, bottom: f32) -> Self {
+        BlockRange {
+            left: self.left + left,
+            top: self.top + top,
+            width: (self.width - left - right).max(0.0),
+            height: (self.height - top - bottom).max(0.0),
+            approach: self.approach,
+        }
+    }
     pub fn split_width(&self, right_width: f32) -> (Self, Self) {
         let right_width = right_width.min(self.width).max(0.0);
         let left_width = self.width - right_width;
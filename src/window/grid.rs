@@ -0,0 +1,106 @@
+use ::flood::*;
+use ::scribe::Scribe;
+use super::{build_blocklist, Blocklist, BlockRange};
+use super::measure::preferred_length;
+use super::stack::resolve_spans;
+
+// Columns are resolved first, measuring intrinsic columns against provisional row
+// heights measured across the whole grid. Rows are then measured against the resolved
+// column widths.
+pub fn build_grid_blocklist<'a, MsgT>(range: &BlockRange, grid: &Grid, cells: &[(Cell, Flood<MsgT>)], scribe: &Scribe<'a>) -> Blocklist<MsgT> where
+    MsgT: Clone
+{
+    let &Grid::Tracks(ref columns, ref rows, ref column_gap, ref row_gap) = grid;
+    let columns = columns.iter().collect::<Vec<_>>();
+    let rows = rows.iter().collect::<Vec<_>>();
+    let column_gap = column_gap.to_f32(range.width, range.height, scribe);
+    let row_gap = row_gap.to_f32(range.height, range.width, scribe);
+
+    let provisional_heights = resolve_spans(&rows, range.height, range.width, row_gap, scribe, |row| {
+        cells.iter()
+            .filter(|&&(cell, _)| cell.row == row && cell.row_span <= 1)
+            .map(|&(_, ref child)| preferred_length(child, Axis::Vertical, range.width, scribe))
+            .fold(0.0, f32::max)
+    });
+    let widths = resolve_spans(&columns, range.width, range.height, column_gap, scribe, |column| {
+        cells.iter()
+            .filter(|&&(cell, _)| cell.column == column && cell.column_span <= 1)
+            .map(|&(cell, ref child)| {
+                let height = provisional_heights.get(cell.row).cloned().unwrap_or(0.0);
+                preferred_length(child, Axis::Horizontal, height, scribe)
+            })
+            .fold(0.0, f32::max)
+    });
+    let heights = resolve_spans(&rows, range.height, range.width, row_gap, scribe, |row| {
+        cells.iter()
+            .filter(|&&(cell, _)| cell.row == row && cell.row_span <= 1)
+            .map(|&(cell, ref child)| {
+                let width = widths.get(cell.column).cloned().unwrap_or(0.0);
+                preferred_length(child, Axis::Vertical, width, scribe)
+            })
+            .fold(0.0, f32::max)
+    });
+    let column_starts = track_starts(&widths, column_gap);
+    let row_starts = track_starts(&heights, row_gap);
+
+    let mut blocklist = Blocklist { max_approach: range.approach, ..Default::default() };
+    for &(cell, ref child) in cells {
+        let covered = (cover(&column_starts, &widths, cell.column, cell.column_span), cover(&row_starts, &heights, cell.row, cell.row_span));
+        if let (Some((left, width)), Some((top, height))) = covered {
+            let (h_align, v_align) = cell.align;
+            let (left, width) = align(h_align, child, Axis::Horizontal, left, width, height, scribe);
+            let (top, height) = align(v_align, child, Axis::Vertical, top, height, width, scribe);
+            let cell_range = BlockRange { left: range.left + left, top: range.top + top, width, height, approach: range.approach };
+            blocklist = blocklist.append(&mut build_blocklist(&cell_range, child, scribe));
+        }
+    }
+    blocklist
+}
+
+fn track_starts(lengths: &[f32], gap: f32) -> Vec<f32> {
+    lengths.iter()
+        .scan(0.0, |start, &length| {
+            let track_start = *start;
+            *start += length + gap;
+            Some(track_start)
+        })
+        .collect()
+}
+
+// Start and length of the tracks a cell covers, including the gaps between them. Spans
+// running past the last track stop there and cells starting past it cover nothing.
+fn cover(starts: &[f32], lengths: &[f32], first: usize, span: usize) -> Option<(f32, f32)> {
+    if first >= lengths.len() {
+        return None;
+    }
+    let last = (first + span.max(1)).min(lengths.len()) - 1;
+    Some((starts[first], starts[last] + lengths[last] - starts[first]))
+}
+
+fn align<'a, MsgT>(cross: Cross, child: &Flood<MsgT>, axis: Axis, start: f32, length: f32, cross_length: f32, scribe: &Scribe<'a>) -> (f32, f32) where
+    MsgT: Clone
+{
+    match cross {
+        Cross::Fill => (start, length),
+        Cross::Place(placement) => {
+            let preferred = preferred_length(child, axis, cross_length, scribe).min(length);
+            let placement: f32 = placement.into();
+            (start + (length - preferred) * placement, preferred)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn spanning_cell_covers_gaps_between_tracks() {
+        let lengths = vec![10.0, 20.0, 30.0];
+        let starts = track_starts(&lengths, 5.0);
+        assert_eq!(vec![0.0, 15.0, 40.0], starts);
+        assert_eq!(Some((15.0, 55.0)), cover(&starts, &lengths, 1, 2));
+        assert_eq!(Some((40.0, 30.0)), cover(&starts, &lengths, 2, 4));
+        assert_eq!(None, cover(&starts, &lengths, 3, 1));
+    }
+}
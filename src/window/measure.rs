@@ -0,0 +1,254 @@
+use ::flood::*;
+use ::scribe::Scribe;
+
+// Preferred width and height of a flood within a maximum width and height. The height is
+// measured first against the maximum width and the width is then measured against that
+// height.
+pub fn preferred_size<'a, MsgT>(flood: &Flood<MsgT>, max_width: f32, max_height: f32, scribe: &Scribe<'a>) -> (f32, f32) where
+    MsgT: Clone
+{
+    let height = preferred_length(flood, Axis::Vertical, max_width, scribe).min(max_height);
+    let width = preferred_length(flood, Axis::Horizontal, height, scribe).min(max_width);
+    (width, height)
+}
+
+// Preferred length of a flood along an axis, given its length across that axis. Text
+// prefers the lines it wraps into at the em within its width, and a single line as wide as
+// its height allows. Stacks, grids and barriers add their parts along their axis and
+// padding adds to its content. Floods with no content-driven size prefer zero.
+pub fn preferred_length<'a, MsgT>(flood: &Flood<MsgT>, axis: Axis, cross_length: f32, scribe: &Scribe<'a>) -> f32 where
+    MsgT: Clone
+{
+    match flood {
+        &Flood::Text(ref text, _, _) => match axis {
+            Axis::Horizontal => {
+                let line_height = scribe.line_height();
+                if cross_length >= 2.0 * line_height {
+                    line_height * scribe.size_text(text)
+                } else {
+                    cross_length * scribe.size_text(text)
+                }
+            }
+            Axis::Vertical => scribe.count_lines(text, cross_length) as f32 * scribe.line_height(),
+        },
+        &Flood::Vessel(Padding::Rim(ref length), ref flood) => {
+            let pad = length.to_f32(cross_length, cross_length, scribe).max(0.0).min(cross_length / 2.0);
+            let content = preferred_length(flood, axis, cross_length - 2.0 * pad, scribe);
+            if content + 2.0 * pad >= cross_length {
+                content + 2.0 * pad
+            } else {
+                padded_length(content, &[length, length], cross_length, scribe)
+            }
+        }
+        &Flood::Vessel(ref padding, ref flood) => {
+            let (along, across) = padding_lengths(padding, axis);
+            let across = across.iter().map(|length| length.to_f32(cross_length, 0.0, scribe)).sum::<f32>();
+            let content = preferred_length(flood, axis, (cross_length - across).max(0.0), scribe);
+            padded_length(content, &along, cross_length, scribe)
+        }
+        &Flood::Stream(ref stack, ref children) => {
+            let spacing = match stack {
+                &Stack::Row(ref spacing, _) => spacing,
+                &Stack::Column(ref spacing, _) => spacing,
+            };
+            if stack.axis() == axis {
+                let spacing = spacing.to_f32(0.0, cross_length, scribe);
+                let lengths = children.iter()
+                    .map(|&(ref span, ref child)| match span {
+                        &Span::Fixed(ref length) => length.to_f32(0.0, cross_length, scribe),
+                        _ => preferred_length(child, axis, cross_length, scribe),
+                    })
+                    .sum::<f32>();
+                lengths + spacing * (children.len().max(1) - 1) as f32
+            } else {
+                children.iter()
+                    .map(|&(ref span, ref child)| {
+                        let child_length = match span {
+                            &Span::Fixed(ref length) => length.to_f32(cross_length, 0.0, scribe),
+                            _ => cross_length,
+                        };
+                        preferred_length(child, axis, child_length, scribe)
+                    })
+                    .fold(0.0, f32::max)
+            }
+        }
+        &Flood::TextSize(TextSize::Em(ref length), ref flood) => {
+            let em = length.to_f32(scribe.em(), 0.0, scribe);
+            scribe.with_em(em, || preferred_length(flood, axis, cross_length, scribe))
+        }
+        &Flood::Confine(ref confine, ref flood) => {
+            let content = match (axis, confine.ratio) {
+                (Axis::Horizontal, Some(ratio)) if ratio > 0.0 => cross_length * ratio,
+                (Axis::Vertical, Some(ratio)) if ratio > 0.0 => cross_length / ratio,
+                _ => preferred_length(flood, axis, cross_length, scribe),
+            };
+            let max_length = match axis {
+                Axis::Horizontal => &confine.max_width,
+                Axis::Vertical => &confine.max_height,
+            };
+            max_length.as_ref().map_or(content, |length| content.min(length.to_f32(content, cross_length, scribe)))
+        }
+        &Flood::Grid(ref grid, ref cells) => grid_length(grid, cells, axis, cross_length, scribe),
+        &Flood::Barrier(ref position, ref a_flood, ref b_flood) => {
+            let (length, position_axis) = match position {
+                &Position::Left(ref length) | &Position::Right(ref length) => (length, Axis::Horizontal),
+                &Position::Top(ref length) | &Position::Bottom(ref length) => (length, Axis::Vertical),
+            };
+            if position_axis == axis {
+                barrier_length(length, preferred_length(b_flood, axis, cross_length, scribe), preferred_length(a_flood, axis, cross_length, scribe), cross_length, scribe)
+            } else {
+                preferred_length(a_flood, axis, cross_length, scribe).max(preferred_length(b_flood, axis, cross_length, scribe))
+            }
+        }
+        &Flood::Sediment(_, ref far, ref near) => {
+            preferred_length(far, axis, cross_length, scribe).max(preferred_length(near, axis, cross_length, scribe))
+        }
+        &Flood::Ripple(_, ref flood) |
+        &Flood::Shade(_, ref flood) |
+        &Flood::Clip(_, ref flood) |
+        &Flood::Fade(_, ref flood) |
+        &Flood::Drift(_, ref flood) |
+        &Flood::Round(_, ref flood) |
+        &Flood::Rim(_, ref flood) |
+        &Flood::Direction(_, ref flood) |
+        &Flood::Float(_, ref flood, _) => preferred_length(flood, axis, cross_length, scribe),
+        _ => 0.0,
+    }
+}
+
+// Text is drawn on one line as tall as its range. Text too long for that line wraps at the
+// em instead when the range is at least two lines tall and holds all of the lines.
+pub fn text_line_height<'a>(text: &str, width: f32, height: f32, scribe: &Scribe<'a>) -> f32 {
+    let line_height = scribe.line_height();
+    if height * scribe.size_text(text) <= width || height < 2.0 * line_height {
+        height
+    } else if scribe.count_lines(text, width) as f32 * line_height <= height {
+        scribe.em()
+    } else {
+        height
+    }
+}
+
+// Padding on either side along the axis and on either side across it.
+fn padding_lengths(padding: &Padding, axis: Axis) -> (Vec<&Length>, Vec<&Length>) {
+    let (horizontal, vertical) = match padding {
+        &Padding::Uniform(ref length) => (vec![length, length], vec![length, length]),
+        &Padding::Dual(ref h_length, ref v_length) => (vec![h_length, h_length], vec![v_length, v_length]),
+        &Padding::Horizontal(ref length) => (vec![length, length], vec![]),
+        &Padding::Vertical(ref length) => (vec![], vec![length, length]),
+        &Padding::Behind(_) => (vec![], vec![]),
+        &Padding::Insets(Insets::Sides(ref left, ref top, ref right, ref bottom)) => (vec![left, right], vec![top, bottom]),
+        &Padding::Rim(ref length) => (vec![length, length], vec![length, length]),
+    };
+    match axis {
+        Axis::Horizontal => (horizontal, vertical),
+        Axis::Vertical => (vertical, horizontal),
+    }
+}
+
+// Padding may be relative to the padded length itself, as in Length::Full / 4. Taking the
+// padding as linear in the padded length, solve for a length that holds the content
+// between the pads.
+fn padded_length<'a>(content: f32, lengths: &[&Length], cross_length: f32, scribe: &Scribe<'a>) -> f32 {
+    let pads = |padded_length: f32| lengths.iter().map(|length| length.to_f32(padded_length, cross_length, scribe)).sum::<f32>();
+    let pad = pads(0.0);
+    let slope = pads(1.0) - pad;
+    if slope < 1.0 {
+        (content + pad) / (1.0 - slope)
+    } else {
+        content + pad
+    }
+}
+
+// The barrier's segment takes its length of the whole, which may be relative to the whole
+// as in Length::Full / 2, and the rest takes what remains. Find the least whole that holds
+// both, taking the segment as linear in the whole.
+fn barrier_length<'a>(length: &Length, segment: f32, rest: f32, cross_length: f32, scribe: &Scribe<'a>) -> f32 {
+    let fixed = length.to_f32(0.0, cross_length, scribe);
+    let slope = length.to_f32(1.0, cross_length, scribe) - fixed;
+    if slope <= 0.0 {
+        fixed.max(segment) + rest
+    } else if slope < 1.0 {
+        ((segment - fixed) / slope).max((rest + fixed) / (1.0 - slope))
+    } else {
+        ((segment - fixed) / slope).max(rest + fixed)
+    }
+}
+
+// Fixed and intrinsic tracks along the axis plus the gaps between them. Fractional and
+// weighted tracks only take up space the grid is given.
+fn grid_length<'a, MsgT>(grid: &Grid, cells: &[(Cell, Flood<MsgT>)], axis: Axis, cross_length: f32, scribe: &Scribe<'a>) -> f32 where
+    MsgT: Clone
+{
+    let &Grid::Tracks(ref columns, ref rows, ref column_gap, ref row_gap) = grid;
+    let (tracks, gap) = match axis {
+        Axis::Horizontal => (columns, column_gap),
+        Axis::Vertical => (rows, row_gap),
+    };
+    let gap = gap.to_f32(0.0, cross_length, scribe);
+    let track_lengths = tracks.iter().enumerate()
+        .map(|(track, span)| match span {
+            &Span::Fixed(ref length) => length.to_f32(0.0, cross_length, scribe),
+            &Span::Intrinsic => cells.iter()
+                .filter(|&&(cell, _)| {
+                    let (start, span) = match axis {
+                        Axis::Horizontal => (cell.column, cell.column_span),
+                        Axis::Vertical => (cell.row, cell.row_span),
+                    };
+                    start == track && span <= 1
+                })
+                .map(|&(_, ref child)| preferred_length(child, axis, cross_length, scribe))
+                .fold(0.0, f32::max),
+            _ => 0.0,
+        })
+        .sum::<f32>();
+    track_lengths + gap * (tracks.len().max(1) - 1) as f32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn relative_padding_is_solved_around_fixed_content() {
+        let scribe = Scribe::default();
+        let line = Flood::Stream::<()>(Stack::Column(Length::Zero, Cross::Fill), vec![
+            (Span::Fixed(Length::Pixels(18.0)), Flood::Color(Default::default())),
+        ]);
+        let flood = line + Padding::Dual(Length::Spacing, Length::Full / 4);
+        let (width, height) = preferred_size(&flood, 1000.0, 1000.0, &scribe);
+        assert_eq!((32.0, 36.0), (width, height));
+        assert_eq!((32.0, 20.0), preferred_size(&flood, 1000.0, 20.0, &scribe));
+        let sided = Flood::Color::<()>(Default::default()) + Insets::Sides(Length::Spacing, Length::Pixels(24.0), Length::Pixels(8.0), Length::Zero);
+        assert_eq!((24.0, 24.0), preferred_size(&sided, 1000.0, 1000.0, &scribe));
+    }
+
+    #[test]
+    fn text_prefers_a_line_at_the_em_and_wraps_within_its_width() {
+        let scribe = Scribe::default();
+        let text = "Wrapping text";
+        let flood = Flood::Text::<()>(text.into(), Default::default(), Placement::Start);
+        let (line_height, line_width) = (scribe.line_height(), scribe.line_height() * scribe.size_text(text));
+        assert_eq!((line_width, line_height), preferred_size(&flood, 1000.0, 1000.0, &scribe));
+        assert_eq!(10.0 * scribe.size_text(text), preferred_length(&flood, Axis::Horizontal, 10.0, &scribe));
+        let wrapped_height = preferred_length(&flood, Axis::Vertical, line_width / 2.0 + 1.0, &scribe);
+        assert!(wrapped_height >= 2.0 * line_height);
+        assert_eq!(scribe.em(), text_line_height(text, line_width / 2.0 + 1.0, wrapped_height, &scribe));
+        let column = Flood::Stream::<()>(Stack::Column(Length::Zero, Cross::Fill), vec![(Span::Intrinsic, flood)]);
+        assert_eq!(line_height, preferred_length(&column, Axis::Vertical, 1000.0, &scribe));
+    }
+
+    #[test]
+    fn relative_barriers_hold_both_sides() {
+        let scribe = Scribe::default();
+        let row = |width: f32| Flood::Stream::<()>(Stack::Row(Length::Zero, Cross::Fill), vec![
+            (Span::Fixed(Length::Pixels(width)), Flood::Color(Default::default())),
+        ]);
+        let half = row(0.0) + (Position::Right(Length::Full / 2), row(40.0));
+        assert_eq!(80.0, preferred_length(&half, Axis::Horizontal, 100.0, &scribe));
+        let quarter = row(60.0) + (Position::Left(Length::Full / 4), row(10.0));
+        assert_eq!(80.0, preferred_length(&quarter, Axis::Horizontal, 100.0, &scribe));
+        let fixed = row(60.0) + (Position::Right(Length::Pixels(20.0)), row(30.0));
+        assert_eq!(90.0, preferred_length(&fixed, Axis::Horizontal, 100.0, &scribe));
+    }
+}
@@ -1,6 +1,7 @@
 use ::{director, DirectorMsg};
 use ::{screen, ScreenMsg};
-use ::{Anchor, Block, Color, Sigil};
+use ::{Anchor, Block, Color, Lighting, Sigil};
+use ::base::Rectangle;
 use ::flood::*;
 pub use ::screen::MAX_APPROACH;
 pub use ::TouchMsg;
@@ -14,6 +15,10 @@ use std::fmt;
 use ::scribe::Scribe;
 
 mod blockrange;
+mod popup;
+pub mod measure;
+mod stack;
+mod grid;
 mod blocklist;
 mod open_window;
 mod keymap;
@@ -25,11 +30,14 @@ pub enum WindowMsg<MsgT> where
     Observe(Sender<MsgT>),
     WindowNote(WindowNote),
     Title(String),
+    Lighting(Lighting),
+    Direction(Direction),
 }
 
 pub enum WindowNote {
     Screen(Sender<ScreenMsg>),
     Range(f32, f32, f32, f32),
+    DpiFactor(f32),
     Touch(TouchMsg),
     Key(VirtualKeyCode),
 }
@@ -66,6 +74,10 @@ pub fn start<MsgT, F>(width: u32, height: u32, on_start: F) where
                 send_window_note(WindowNote::Range(0.0, 0.0, new_width as f32, new_height as f32));
                 ((), director::ControlFlow::Continue)
             }
+            DirectorMsg::ScreenDpiFactor(dpi_factor) => {
+                send_window_note(WindowNote::DpiFactor(dpi_factor));
+                ((), director::ControlFlow::Continue)
+            }
             DirectorMsg::ScreenClosed => {
                 ((), director::ControlFlow::Break)
             }
@@ -94,6 +106,13 @@ fn spawn_window<MsgT>(range: BlockRange, seed: Option<u64>) -> Sender<WindowMsg<
                 WindowMsg::Title(string) => {
                     open_window.set_title(&string);
                 }
+                WindowMsg::Lighting(lighting) => {
+                    open_window.set_lighting(lighting);
+                }
+                WindowMsg::Direction(direction) => {
+                    open_window.scribe.set_direction(direction);
+                    open_window.cycle();
+                }
                 WindowMsg::Flood(flood) => {
                     open_window.flood = flood;
                     open_window.cycle();
@@ -114,6 +133,10 @@ fn spawn_window<MsgT>(range: BlockRange, seed: Option<u64>) -> Sender<WindowMsg<
                             open_window.range.height = height;
                             open_window.cycle();
                         }
+                        WindowNote::DpiFactor(dpi_factor) => {
+                            open_window.scribe.set_dpi_factor(dpi_factor);
+                            open_window.cycle();
+                        }
                         WindowNote::Touch(touch_msg) => {
                             open_window.touch(touch_msg);
                         }
@@ -134,9 +157,8 @@ pub fn build_blocklist<'a, MsgT>(range: &BlockRange, flood: &Flood<MsgT>, scribe
     match flood {
         &Flood::Escape(ref raft) => {
             let mut blocklist = build_placeholder_blocklist::<MsgT>(range, scribe);
-            let &Raft::RangeAdapter(tag, ref range_adapter) = raft;
-            let raft_msg = range_adapter(tag, &range.with_approach(blocklist.max_approach + 1.0));
-            blocklist.raft_msgs.push(raft_msg);
+            let raft_range = range.with_approach(blocklist.max_approach + 1.0);
+            blocklist.rafts.push((raft.clone(), raft_range));
             blocklist
         }
         &Flood::Ripple(Sensor::Timeout(ref versioned_timeout), ref flood) => {
@@ -162,15 +184,78 @@ pub fn build_blocklist<'a, MsgT>(range: &BlockRange, flood: &Flood<MsgT>, scribe
                 height: range.height,
                 anchor: Anchor { x: range.left, y: range.top },
                 approach: blocklist.max_approach,
+                ..Default::default()
             };
             let touch_adapter = (tag, adapter.clone());
             blocklist.push_block(block);
             blocklist.touch_adapters.push(touch_adapter);
             blocklist
         }
+        &Flood::Shade(Shadow::Clear, ref flood) => {
+            let mut blocklist = build_blocklist(range, flood, scribe);
+            blocklist.blocks.iter_mut().for_each(|block| block.casts_shadow = false);
+            blocklist
+        }
+        &Flood::Clip(Clip::Range, ref flood) => {
+            let mut blocklist = build_blocklist(range, flood, scribe);
+            let bounds = Rectangle { left: range.left, right: range.left + range.width, top: range.top, bottom: range.top + range.height };
+            for block in blocklist.blocks.iter_mut() {
+                block.clip = Some(block.clip.map_or(bounds, |clip| clip.intersect(&bounds)));
+            }
+            blocklist
+        }
+        &Flood::TextSize(TextSize::Em(ref length), ref flood) => {
+            let em = length.to_f32(scribe.em(), 0.0, scribe);
+            scribe.with_em(em, || build_blocklist(range, flood, scribe))
+        }
+        &Flood::Direction(direction, ref flood) => {
+            if direction == scribe.direction() {
+                build_blocklist(range, flood, scribe)
+            } else {
+                let mut blocklist = scribe.with_direction(direction, || build_blocklist(range, flood, scribe));
+                blocklist.mirror(range);
+                blocklist
+            }
+        }
+        &Flood::Confine(ref confine, ref flood) => build_blocklist(&confine.place_in(range, scribe), flood, scribe),
+        &Flood::Stream(ref stack, ref children) => stack::build_stack_blocklist(range, stack, children, scribe),
+        &Flood::Grid(ref grid, ref cells) => grid::build_grid_blocklist(range, grid, cells, scribe),
+        &Flood::Float(ref popup, ref anchor_flood, ref popup_flood) => {
+            let mut blocklist = build_blocklist(range, anchor_flood, scribe);
+            blocklist.popups.push((*range, popup.clone(), (**popup_flood).clone(), scribe.em(), scribe.direction()));
+            blocklist
+        }
+        &Flood::Fade(Fade::Alpha(alpha), ref flood) => {
+            let mut blocklist = build_blocklist(range, flood, scribe);
+            for block in blocklist.blocks.iter_mut() {
+                block.sigil = block.sigil.with_opacity(alpha);
+            }
+            blocklist
+        }
+        &Flood::Drift(Drift::By(ref h_length, ref v_length), ref flood) => {
+            let h_offset = h_length.to_f32(range.width, range.height, scribe);
+            let v_offset = v_length.to_f32(range.height, range.width, scribe);
+            build_blocklist(&range.with_offset(h_offset, v_offset), flood, scribe)
+        }
+        &Flood::Round(ref corners, ref flood) => {
+            let mut blocklist = build_blocklist(range, flood, scribe);
+            for block in blocklist.blocks.iter_mut() {
+                let radii = corners.to_radii(block.width, block.height, scribe);
+                block.sigil = block.sigil.with_corners(radii);
+            }
+            blocklist
+        }
+        &Flood::Rim(Border::Solid(ref length, color), ref flood) => {
+            let mut blocklist = build_blocklist(range, flood, scribe);
+            for block in blocklist.blocks.iter_mut() {
+                let border_width = rim_width(length, block.width, block.height, scribe);
+                block.sigil = block.sigil.with_border(border_width, color);
+            }
+            blocklist
+        }
         &Flood::Sediment(ref silt, ref far_flood, ref near_flood) => {
             let mut far_blocklist = build_blocklist(range, far_flood, scribe);
-            let near_approach = silt.add_to(far_blocklist.max_approach);
+            let near_approach = silt.add_to_range(range.approach, far_blocklist.max_approach);
             let mut near_blocklist = build_blocklist(&range.with_approach(near_approach), near_flood, scribe);
             far_blocklist.append(&mut near_blocklist)
         }
@@ -197,6 +282,15 @@ pub fn build_blocklist<'a, MsgT>(range: &BlockRange, flood: &Flood<MsgT>, scribe
                     let v_pad = length.to_f32(range.height, range.width, scribe);
                     build_blocklist(&range.with_padding(0.0, v_pad), flood, scribe)
                 }
+                &Padding::Insets(Insets::Sides(ref left, ref top, ref right, ref bottom)) => {
+                    let (left, right) = (left.to_f32(range.width, range.height, scribe), right.to_f32(range.width, range.height, scribe));
+                    let (top, bottom) = (top.to_f32(range.height, range.width, scribe), bottom.to_f32(range.height, range.width, scribe));
+                    build_blocklist(&range.with_insets(left, top, right, bottom), flood, scribe)
+                }
+                &Padding::Rim(ref length) => {
+                    let pad = rim_width(length, range.width, range.height, scribe);
+                    build_blocklist(&range.with_padding(pad, pad), flood, scribe)
+                }
             }
         }
         &Flood::Barrier(ref position, ref a_flood, ref b_flood) => {
@@ -226,14 +320,41 @@ pub fn build_blocklist<'a, MsgT>(range: &BlockRange, flood: &Flood<MsgT>, scribe
         &Flood::Text(ref string, color, placement) => {
             let &BlockRange { left, top, width, height, approach } = range;
             let sigil = Sigil::Paragraph {
-                line_height: height,
+                line_height: measure::text_line_height(string, width, height, scribe),
                 text: string.to_owned(),
                 color,
                 placement: placement.into(),
             };
             Blocklist {
                 max_approach: approach,
-                blocks: vec![Block { sigil, width, height, anchor: Anchor { x: left, y: top }, approach }],
+                blocks: vec![Block { sigil, width, height, anchor: Anchor { x: left, y: top }, approach, ..Default::default() }],
+                ..Default::default()
+            }
+        }
+        &Flood::Image(ref source, fit, tint) => {
+            let &BlockRange { left, top, width, height, approach } = range;
+            let sigil = Sigil::Image { source: source.clone(), fit, tint };
+            Blocklist {
+                max_approach: approach,
+                blocks: vec![Block { sigil, width, height, anchor: Anchor { x: left, y: top }, approach, ..Default::default() }],
+                ..Default::default()
+            }
+        }
+        &Flood::Canvas(ref paths, ref inks) => {
+            let &BlockRange { left, top, width, height, approach } = range;
+            let sigil = Sigil::Canvas { paths: paths.clone(), inks: inks.clone() };
+            Blocklist {
+                max_approach: approach,
+                blocks: vec![Block { sigil, width, height, anchor: Anchor { x: left, y: top }, approach, ..Default::default() }],
+                ..Default::default()
+            }
+        }
+        &Flood::Gradient(ref gradient) => {
+            let &BlockRange { left, top, width, height, approach } = range;
+            let sigil = Sigil::Gradient(gradient.clone());
+            Blocklist {
+                max_approach: approach,
+                blocks: vec![Block { sigil, width, height, anchor: Anchor { x: left, y: top }, approach, ..Default::default() }],
                 ..Default::default()
             }
         }
@@ -242,13 +363,46 @@ pub fn build_blocklist<'a, MsgT>(range: &BlockRange, flood: &Flood<MsgT>, scribe
             let sigil = Sigil::Color(color);
             Blocklist {
                 max_approach: approach,
-                blocks: vec![Block { sigil, width, height, anchor: Anchor { x: left, y: top }, approach }],
+                blocks: vec![Block { sigil, width, height, anchor: Anchor { x: left, y: top }, approach, ..Default::default() }],
                 ..Default::default()
             }
         }
     }
 }
 
+// Rims resolve against the short side of what they surround and fill at most half of it.
+fn rim_width<'a>(length: &Length, width: f32, height: f32, scribe: &Scribe<'a>) -> f32 {
+    let (short, long) = (width.min(height), width.max(height));
+    length.to_f32(short, long, scribe).max(0.0).min(short / 2.0)
+}
+
+// Builds a whole window. Popups are collected while building and laid out afterwards above
+// everything built so far, so each popup, and any popup it opens, covers the window. Each
+// popup keeps the em and direction of the subtree that raised it.
+pub fn build_window_blocklist<'a, MsgT>(window: &BlockRange, flood: &Flood<MsgT>, scribe: &Scribe<'a>) -> Blocklist<MsgT> where
+    MsgT: Clone
+{
+    let mut blocklist = build_blocklist(window, flood, scribe);
+    if scribe.direction() == Direction::RightToLeft {
+        blocklist.mirror(window);
+    }
+    while !blocklist.popups.is_empty() {
+        let popups = blocklist.popups.drain(..).collect::<Vec<_>>();
+        for (anchor, popup, popup_flood, em, direction) in popups {
+            let approach = Stratum::Overlay.add_to(blocklist.max_approach);
+            let popup_range = scribe.with_em(em, || popup::place(&popup, &anchor, window, scribe)).with_approach(approach);
+            let mut popup_blocklist = scribe.with_direction(direction, || {
+                scribe.with_em(em, || build_blocklist(&popup_range, &popup_flood, scribe))
+            });
+            if direction == Direction::RightToLeft {
+                popup_blocklist.mirror(&popup_range);
+            }
+            blocklist = blocklist.append(&mut popup_blocklist);
+        }
+    }
+    blocklist
+}
+
 fn build_placeholder_blocklist<'a, MsgT>(range: &BlockRange, scribe: &Scribe<'a>) -> Blocklist<MsgT> where
     MsgT: Clone
 {
@@ -257,3 +411,74 @@ fn build_placeholder_blocklist<'a, MsgT>(range: &BlockRange, scribe: &Scribe<'a>
     blocklist.update_max_approach(range.approach);
     blocklist
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+
+    fn test_range() -> BlockRange {
+        BlockRange { left: 10.0, top: 20.0, width: 100.0, height: 50.0, approach: 0.0 }
+    }
+
+    #[test]
+    fn fade_multiplies_alpha_within_bounds() {
+        let scribe = Scribe::default();
+        let faded_alpha = |alpha: f32| {
+            let flood = Flood::Color::<()>(Color { a: 0.5, ..Color::black() }) + Fade::Alpha(alpha);
+            match build_blocklist(&test_range(), &flood, &scribe).blocks[0].sigil {
+                Sigil::Color(color) => color.a,
+                _ => panic!("Faded color is not a color"),
+            }
+        };
+        assert_eq!(0.25, faded_alpha(0.5));
+        assert_eq!(0.5, faded_alpha(2.0));
+        assert_eq!(0.0, faded_alpha(-1.0));
+    }
+
+    #[test]
+    fn frame_insets_content_by_the_drawn_border() {
+        let scribe = Scribe::default();
+        let frame = Frame {
+            margin: Insets::uniform(Length::Pixels(10.0)),
+            border: Some(Border::Solid(Length::Full / 20, Color::black())),
+            background: Some(Color::white()),
+            padding: Insets::uniform(Length::Pixels(5.0)),
+        };
+        let range = BlockRange { left: 0.0, top: 0.0, width: 200.0, height: 100.0, approach: 0.0 };
+        let blocklist = build_blocklist(&range, &(Flood::Color::<()>(Color::grey()) + frame), &scribe);
+        let bounds = |block: &Block| (block.anchor.x, block.anchor.y, block.width, block.height);
+        let backing = blocklist.blocks.iter().find(|block| block.sigil != Sigil::Color(Color::grey())).unwrap();
+        let content = blocklist.blocks.iter().find(|block| block.sigil == Sigil::Color(Color::grey())).unwrap();
+        assert_eq!((10.0, 10.0, 180.0, 80.0), bounds(backing));
+        assert_eq!(Sigil::Color(Color::white()).with_border(4.0, Color::black()), backing.sigil);
+        assert_eq!((19.0, 19.0, 162.0, 62.0), bounds(content));
+    }
+
+    #[test]
+    fn left_to_right_subtree_keeps_its_order_in_a_right_to_left_window() {
+        let mut scribe = Scribe::default();
+        scribe.set_direction(Direction::RightToLeft);
+        let window = BlockRange { left: 0.0, top: 0.0, width: 100.0, height: 50.0, approach: 0.0 };
+        let row = |children: Vec<(f32, Flood<f32>)>| Flood::Stream(Stack::Row(Length::Zero, Cross::Fill), children.into_iter()
+            .map(|(width, child)| (Span::Fixed(Length::Pixels(width)), child))
+            .collect());
+        let escape = Flood::Escape(Raft::RangeAdapter(1, Arc::new(|_, range: &BlockRange| range.left)));
+        let nested = row(vec![(20.0, Flood::Color(Color::white())), (50.0, Flood::Color(Color::grey()))]) + Direction::LeftToRight;
+        let flood = row(vec![(10.0, escape), (20.0, Flood::Color(Color::black())), (70.0, nested)]);
+        let blocklist = build_window_blocklist(&window, &flood, &scribe);
+        let left_of = |color: Color| blocklist.blocks.iter().find(|block| block.sigil == Sigil::Color(color)).unwrap().anchor.x;
+        assert_eq!((70.0, 0.0, 20.0), (left_of(Color::black()), left_of(Color::white()), left_of(Color::grey())));
+        assert_eq!(vec![90.0], blocklist.raft_msgs());
+    }
+
+    #[test]
+    fn drift_offsets_anchors_by_range_relative_lengths() {
+        let scribe = Scribe::default();
+        let flood = Flood::Color::<()>(Color::black()) + Drift::By(Length::Full / 10, Length::Pixels(-5.0));
+        let blocklist = build_blocklist(&test_range(), &flood, &scribe);
+        let block = &blocklist.blocks[0];
+        assert_eq!((20.0, 15.0), (block.anchor.x, block.anchor.y));
+        assert_eq!((100.0, 50.0), (block.width, block.height));
+    }
+}
@@ -1,4 +1,4 @@
-use ::{Color, ScreenMsg, TouchMsg};
+use ::{Color, Lighting, ScreenMsg, TouchMsg};
 use ::flood::{Duration, Flood, Input, Signal, Timeout, Version};
 use ::scribe::Scribe;
 use ::window::{BlockRange, VirtualKeyCode};
@@ -7,7 +7,7 @@ use std::collections::HashSet;
 use std::fmt;
 use std::sync::Arc;
 use std::sync::mpsc::Sender;
-use super::build_blocklist;
+use super::build_window_blocklist;
 use super::keymap;
 
 
@@ -25,6 +25,7 @@ pub struct OpenWindow<'a, MsgT> where
     pub signals: HashMap<u64, Signal<MsgT>>,
     pub timeouts: HashMap<u64, Version<Timeout<MsgT>>>,
     pub title: Option<String>,
+    pub lighting: Option<Lighting>,
     pub scribe: Scribe<'a>,
 }
 
@@ -44,6 +45,7 @@ impl<'a, MsgT> OpenWindow<'a, MsgT> where
             signals: HashMap::new(),
             timeouts: HashMap::new(),
             title: None,
+            lighting: None,
             scribe: Scribe::default(),
         }
     }
@@ -58,9 +60,21 @@ impl<'a, MsgT> OpenWindow<'a, MsgT> where
         }
     }
 
+    pub fn set_lighting(&mut self, lighting: Lighting) {
+        self.lighting = Some(lighting);
+        self.send_lighting_to_screen();
+    }
+
+    fn send_lighting_to_screen(&self) {
+        if let (Some(lighting), &Some(ref screen)) = (self.lighting, &self.screen) {
+            screen.send(ScreenMsg::Lighting(lighting)).unwrap();
+        }
+    }
+
     pub fn set_screen(&mut self, screen: Sender<ScreenMsg>) {
         self.screen = Some(screen);
         self.send_title_to_screen();
+        self.send_lighting_to_screen();
         self.cycle();
     }
 
@@ -97,14 +111,15 @@ impl<'a, MsgT> OpenWindow<'a, MsgT> where
         self.input_adapters.clear();
         if let (Some(screen), Some(seed)) = (self.screen.clone(), self.seed.clone()) {
             let mut old_blocks: HashSet<u64> = self.block_ids.drain(..).collect();
+            self.scribe.set_window(self.range.width, self.range.height);
 
-            let mut blocklist = build_blocklist(&self.range, &self.flood, &self.scribe);
+            let mut blocklist = build_window_blocklist(&self.range, &self.flood, &self.scribe);
 
             self.touch_adapters.append(&mut blocklist.touch_adapters);
             self.input_adapters.append(&mut blocklist.input_adapters);
 
             if let Some(ref observer) = self.observer {
-                blocklist.raft_msgs.into_iter()
+                blocklist.raft_msgs().into_iter()
                     .for_each(|msg| {
                         observer.send(msg).unwrap();
                     });
@@ -0,0 +1,81 @@
+use ::flood::Popup;
+use ::scribe::Scribe;
+use super::BlockRange;
+
+pub fn place<'a>(popup: &Popup, anchor: &BlockRange, window: &BlockRange, scribe: &Scribe<'a>) -> BlockRange {
+    let (width_length, height_length) = match popup {
+        &Popup::Above(ref width, ref height) => (width, height),
+        &Popup::Below(ref width, ref height) => (width, height),
+        &Popup::Left(ref width, ref height) => (width, height),
+        &Popup::Right(ref width, ref height) => (width, height),
+        &Popup::Center(ref width, ref height) => {
+            let width = width.to_f32(window.width, window.height, scribe).min(window.width);
+            let height = height.to_f32(window.height, window.width, scribe).min(window.height);
+            return BlockRange {
+                left: window.left + (window.width - width) / 2.0,
+                top: window.top + (window.height - height) / 2.0,
+                width,
+                height,
+                approach: anchor.approach,
+            };
+        }
+    };
+    let width = width_length.to_f32(anchor.width, anchor.height, scribe).min(window.width);
+    let height = height_length.to_f32(anchor.height, anchor.width, scribe).min(window.height);
+    let (window_right, window_bottom) = (window.left + window.width, window.top + window.height);
+    let (anchor_right, anchor_bottom) = (anchor.left + anchor.width, anchor.top + anchor.height);
+    let (left, top) = match popup {
+        &Popup::Above(_, _) | &Popup::Below(_, _) => {
+            let (above, below) = (anchor.top - height, anchor_bottom);
+            let fits_above = above >= window.top;
+            let fits_below = below + height <= window_bottom;
+            let top = match popup {
+                &Popup::Above(_, _) if fits_above || !fits_below => above,
+                &Popup::Below(_, _) if fits_below || !fits_above => below,
+                &Popup::Above(_, _) => below,
+                _ => above,
+            };
+            (anchor.left, top)
+        }
+        _ => {
+            let (before, after) = (anchor.left - width, anchor_right);
+            let fits_before = before >= window.left;
+            let fits_after = after + width <= window_right;
+            let left = match popup {
+                &Popup::Left(_, _) if fits_before || !fits_after => before,
+                &Popup::Right(_, _) if fits_after || !fits_before => after,
+                &Popup::Left(_, _) => after,
+                _ => before,
+            };
+            (left, anchor.top)
+        }
+    };
+    BlockRange {
+        left: left.min(window_right - width).max(window.left),
+        top: top.min(window_bottom - height).max(window.top),
+        width,
+        height,
+        approach: anchor.approach,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ::flood::Length;
+    use super::*;
+
+    fn range(left: f32, top: f32, width: f32, height: f32) -> BlockRange {
+        BlockRange { left, top, width, height, approach: 0.0 }
+    }
+
+    #[test]
+    fn popup_below_flips_above_at_window_bottom() {
+        let scribe = Scribe::default();
+        let window = range(0.0, 0.0, 100.0, 100.0);
+        let popup = Popup::Below(Length::Full, Length::Pixels(30.0));
+        let near_top = place(&popup, &range(10.0, 10.0, 50.0, 20.0), &window, &scribe);
+        let near_bottom = place(&popup, &range(10.0, 70.0, 50.0, 20.0), &window, &scribe);
+        assert_eq!((10.0, 30.0, 50.0), (near_top.left, near_top.top, near_top.width));
+        assert_eq!((10.0, 40.0), (near_bottom.left, near_bottom.top));
+    }
+}
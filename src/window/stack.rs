@@ -0,0 +1,78 @@
+use ::flood::*;
+use ::scribe::Scribe;
+use super::{build_blocklist, Blocklist, BlockRange};
+use super::measure::preferred_length;
+
+pub fn build_stack_blocklist<'a, MsgT>(range: &BlockRange, stack: &Stack, children: &[(Span, Flood<MsgT>)], scribe: &Scribe<'a>) -> Blocklist<MsgT> where
+    MsgT: Clone
+{
+    let (axis, spacing, cross) = match stack {
+        &Stack::Row(ref spacing, cross) => (Axis::Horizontal, spacing, cross),
+        &Stack::Column(ref spacing, cross) => (Axis::Vertical, spacing, cross),
+    };
+    let (main_length, cross_length) = match axis {
+        Axis::Horizontal => (range.width, range.height),
+        Axis::Vertical => (range.height, range.width),
+    };
+    let spacing = spacing.to_f32(main_length, cross_length, scribe);
+    let spans = children.iter().map(|&(ref span, _)| span).collect::<Vec<_>>();
+    let lengths = resolve_spans(&spans, main_length, cross_length, spacing, scribe, |i| {
+        preferred_length(&children[i].1, axis, cross_length, scribe)
+    });
+
+    let mut start = 0.0;
+    let mut blocklist = Blocklist { max_approach: range.approach, ..Default::default() };
+    for (&(_, ref child), length) in children.iter().zip(lengths.into_iter()) {
+        let (child_cross, cross_offset) = match cross {
+            Cross::Fill => (cross_length, 0.0),
+            Cross::Place(placement) => {
+                let child_cross = preferred_length(child, cross_axis(axis), length, scribe).min(cross_length);
+                let placement: f32 = placement.into();
+                (child_cross, (cross_length - child_cross) * placement)
+            }
+        };
+        let child_range = match axis {
+            Axis::Horizontal => BlockRange { left: range.left + start, top: range.top + cross_offset, width: length, height: child_cross, approach: range.approach },
+            Axis::Vertical => BlockRange { left: range.left + cross_offset, top: range.top + start, width: child_cross, height: length, approach: range.approach },
+        };
+        blocklist = blocklist.append(&mut build_blocklist(&child_range, child, scribe));
+        start += length + spacing;
+    }
+    blocklist
+}
+
+// Lengths of spans laid end to end with spacing between them. Intrinsic spans take the
+// length measured for their index.
+pub fn resolve_spans<'a, F>(spans: &[&Span], main_length: f32, cross_length: f32, spacing: f32, scribe: &Scribe<'a>, intrinsic: F) -> Vec<f32> where
+    F: Fn(usize) -> f32
+{
+    let available = (main_length - spacing * (spans.len().max(1) - 1) as f32).max(0.0);
+    let sized = spans.iter().enumerate()
+        .map(|(i, span)| match *span {
+            &Span::Fixed(ref length) => Some(length.to_f32(main_length, cross_length, scribe)),
+            &Span::Fraction(fraction) => Some(available * fraction),
+            &Span::Intrinsic => Some(intrinsic(i)),
+            &Span::Weight(_) => None,
+        })
+        .collect::<Vec<_>>();
+    let leftover = (available - sized.iter().filter_map(|&length| length).sum::<f32>()).max(0.0);
+    let total_weight = spans.iter()
+        .map(|span| match *span {
+            &Span::Weight(weight) => weight,
+            _ => 0.0,
+        })
+        .sum::<f32>();
+    spans.iter().zip(sized.into_iter())
+        .map(|(span, length)| length.unwrap_or_else(|| match *span {
+            &Span::Weight(weight) if total_weight > 0.0 => leftover * weight / total_weight,
+            _ => 0.0,
+        }))
+        .collect()
+}
+
+fn cross_axis(axis: Axis) -> Axis {
+    match axis {
+        Axis::Horizontal => Axis::Vertical,
+        Axis::Vertical => Axis::Horizontal,
+    }
+}